@@ -4,6 +4,11 @@
 // Used by: settings.html
 // Calls: Tauri invoke commands for settings management
 
+// Commands reject with {code, message, details}; older paths may still reject with a string
+function errorMessage(error) {
+    return (error && error.message) || String(error);
+}
+
 class SettingsManager {
     constructor() {
         this.hasChanges = false;
@@ -52,6 +57,47 @@ class SettingsManager {
         } catch (error) {
             console.error('Failed to load settings:', error);
             this.showError('Failed to load settings');
+            return;
+        }
+        await this.resolveStoredConflicts();
+    }
+    
+    // Offer to merge targets that were stored with the same room (older versions, hand edits)
+    async resolveStoredConflicts() {
+        try {
+            const conflicts = await window.__TAURI__.invoke('get_target_conflicts');
+            for (const conflict of conflicts) {
+                await this.offerMerge(conflict);
+            }
+        } catch (error) {
+            console.error('Failed to check target conflicts:', error);
+        }
+    }
+    
+    // Ask to fold conflict.target_id into conflict.existing_id; returns true if merged
+    async offerMerge(conflict) {
+        const confirmed = await this.showConfirmDialog(
+            'Duplicate Target',
+            `"${this.escapeHtml(conflict.target_label)}" and "${this.escapeHtml(conflict.existing_label)}" join the same room. ` +
+            `Merge them into one target? Labels, notes and hotkeys are combined.`,
+            'Merge'
+        );
+        if (!confirmed) {
+            return false;
+        }
+        
+        try {
+            await window.__TAURI__.invoke('merge_targets', {
+                keepId: conflict.existing_id,
+                removeId: conflict.target_id
+            });
+            await this.refreshTargets();
+            this.showTempMessage('Targets merged', 'success');
+            return true;
+        } catch (error) {
+            console.error('Failed to merge targets:', error);
+            this.showTempMessage(`Failed to merge targets: ${errorMessage(error)}`, 'error');
+            return false;
         }
     }
     
@@ -63,7 +109,11 @@ class SettingsManager {
         }
         
         try {
-            await window.__TAURI__.invoke('save_settings', { settings: this.settings });
+            // Targets are saved as they're edited; only preferences and hotkeys go here
+            await window.__TAURI__.invoke('save_settings', {
+                appSettings: this.settings.app_settings,
+                keybinds: this.settings.keybinds
+            });
             this.hasChanges = false;
             window.toast.success('Settings saved successfully');
             
@@ -73,7 +123,7 @@ class SettingsManager {
             }, 1000);
         } catch (error) {
             console.error('Failed to save settings:', error);
-            window.toast.error(`Failed to save settings: ${error}`);
+            window.toast.error(`Failed to save settings: ${errorMessage(error)}`);
         }
     }
     
@@ -101,6 +151,13 @@ class SettingsManager {
         if (importBtn) {
             importBtn.addEventListener('click', () => this.toggleImportMode());
         }
+        document.getElementById('target-code').addEventListener('paste', () => {
+            // Let the pasted text land in the input first
+            setTimeout(() => this.offerLinkTarget(), 0);
+        });
+        document.querySelectorAll('input[name="target-type"]').forEach(radio => {
+            radio.addEventListener('change', () => this.updateTypeFields(true));
+        });
         
         // Preferences
         document.getElementById('autostart').addEventListener('change', (e) => {
@@ -108,11 +165,48 @@ class SettingsManager {
             this.hasChanges = true;
         });
         
+        document.getElementById('tray-left-click').addEventListener('change', (e) => {
+            this.settings.app_settings.tray_left_click = e.target.value;
+            this.hasChanges = true;
+        });
+        
+        document.getElementById('join-mode').addEventListener('change', (e) => {
+            this.settings.app_settings.join_mode = e.target.value;
+            this.hasChanges = true;
+        });
+        
+        document.getElementById('browser-executable').addEventListener('change', (e) => {
+            this.browserSettings().executable = e.target.value.trim() || null;
+            this.hasChanges = true;
+        });
+        
+        document.getElementById('browser-profile').addEventListener('change', (e) => {
+            this.browserSettings().profile = e.target.value.trim() || null;
+            this.hasChanges = true;
+        });
+        
+        document.getElementById('browser-app-mode').addEventListener('change', (e) => {
+            this.browserSettings().app_mode = e.target.checked;
+            this.hasChanges = true;
+        });
+        
+        document.getElementById('prewarm-conference-window').addEventListener('change', (e) => {
+            this.settings.app_settings.prewarm_conference_window = e.target.checked;
+            this.hasChanges = true;
+        });
+        
         document.getElementById('always-on-top').addEventListener('change', (e) => {
             this.settings.app_settings.always_on_top = e.target.checked;
             this.hasChanges = true;
         });
         
+        document.getElementById('auto-hangup-minutes').addEventListener('change', (e) => {
+            const minutes = Math.max(0, Math.round(Number(e.target.value) || 0));
+            e.target.value = minutes;
+            this.settings.app_settings.auto_hangup_after_secs = minutes * 60;
+            this.hasChanges = true;
+        });
+        
         document.getElementById('play-join-sound').addEventListener('change', (e) => {
             this.settings.app_settings.play_join_sound = e.target.checked;
             this.hasChanges = true;
@@ -120,9 +214,17 @@ class SettingsManager {
         
         document.getElementById('show-notifications').addEventListener('change', (e) => {
             this.settings.app_settings.show_notifications = e.target.checked;
+            this.renderNotificationToggles();
             this.hasChanges = true;
         });
         
+        document.querySelectorAll('[data-notification]').forEach((checkbox) => {
+            checkbox.addEventListener('change', (e) => {
+                this.notificationSettings()[e.target.dataset.notification] = e.target.checked;
+                this.hasChanges = true;
+            });
+        });
+        
 
         
         // Hotkey inputs
@@ -134,6 +236,18 @@ class SettingsManager {
             this.recordHotkey(e.target, 'hangup');
         });
         
+        document.getElementById('toggle-mute').addEventListener('click', (e) => {
+            this.recordHotkey(e.target, 'toggle_mute');
+        });
+        
+        document.getElementById('toggle-video').addEventListener('click', (e) => {
+            this.recordHotkey(e.target, 'toggle_video');
+        });
+        
+        document.getElementById('toggle-mini-mode').addEventListener('click', (e) => {
+            this.recordHotkey(e.target, 'toggle_mini_mode');
+        });
+        
         // Modal close on background click
         document.getElementById('target-modal').addEventListener('click', (e) => {
             if (e.target.id === 'target-modal') {
@@ -193,10 +307,11 @@ class SettingsManager {
             <div class="target-item" data-id="${target.id}">
                 <div class="target-info">
                     <div class="target-label">${this.escapeHtml(target.label)}</div>
-                    <div class="target-code">${target.code}</div>
+                    <div class="target-code">${this.escapeHtml(target.code)}</div>
                     <div class="target-badges">
                         ${target.is_primary ? '<span class="badge primary">Primary</span>' : ''}
                         <span class="badge">${target.target_type}</span>
+                        ${(target.channels || []).map(c => `<span class="badge"># ${this.escapeHtml(c.name)}</span>`).join('')}
                     </div>
                 </div>
                 <div class="target-actions">
@@ -211,17 +326,58 @@ class SettingsManager {
     renderHotkeys() {
         document.getElementById('join-primary').value = this.settings.keybinds.join_primary;
         document.getElementById('hangup').value = this.settings.keybinds.hangup;
+        document.getElementById('toggle-mute').value = this.settings.keybinds.toggle_mute || '';
+        document.getElementById('toggle-video').value = this.settings.keybinds.toggle_video || '';
+        document.getElementById('toggle-mini-mode').value = this.settings.keybinds.toggle_mini_mode || '';
     }
     
     // Render preferences
     renderPreferences() {
         document.getElementById('autostart').checked = this.settings.app_settings.autostart;
+        document.getElementById('tray-left-click').value = this.settings.app_settings.tray_left_click || 'open_settings';
+        document.getElementById('join-mode').value = this.settings.app_settings.join_mode || 'external';
+        const browser = this.browserSettings();
+        document.getElementById('browser-executable').value = browser.executable || '';
+        document.getElementById('browser-profile').value = browser.profile || '';
+        document.getElementById('browser-app-mode').checked = browser.app_mode;
+        document.getElementById('prewarm-conference-window').checked = !!this.settings.app_settings.prewarm_conference_window;
         document.getElementById('always-on-top').checked = this.settings.app_settings.always_on_top;
+        const autoHangupSecs = this.settings.app_settings.auto_hangup_after_secs ?? 300;
+        document.getElementById('auto-hangup-minutes').value = Math.round(autoHangupSecs / 60);
         document.getElementById('play-join-sound').checked = this.settings.app_settings.play_join_sound;
         document.getElementById('show-notifications').checked = this.settings.app_settings.show_notifications;
+        this.renderNotificationToggles();
 
     }
     
+    // Per-event notification toggles, disabled while notifications are off
+    renderNotificationToggles() {
+        const enabled = this.settings.app_settings.show_notifications;
+        const toggles = this.notificationSettings();
+        document.querySelectorAll('[data-notification]').forEach((checkbox) => {
+            checkbox.checked = toggles[checkbox.dataset.notification] !== false;
+            checkbox.disabled = !enabled;
+        });
+    }
+    
+    // app_settings.notifications, created on first use (older settings may lack it)
+    notificationSettings() {
+        if (!this.settings.app_settings.notifications) {
+            this.settings.app_settings.notifications = {
+                join_failed: true, no_primary_target: true, hotkey_failed: true, partner_joined: true,
+            };
+        }
+        return this.settings.app_settings.notifications;
+    }
+    
+    // app_settings.browser, created on first use (older settings may lack it)
+    browserSettings() {
+        if (!this.settings.app_settings.browser) {
+            this.settings.app_settings.browser = { executable: null, profile: null, app_mode: false };
+        }
+        return this.settings.app_settings.browser;
+    }
+    
     // Show add target modal
     async showAddTargetModal() {
         this.editingTargetId = null;
@@ -230,10 +386,13 @@ class SettingsManager {
         // Reset form
         document.getElementById('target-label').value = '';
         document.getElementById('target-notes').value = '';
+        document.getElementById('target-channels').value = '';
         document.querySelector('input[name="target-type"][value="person"]').checked = true;
+        this.updateTypeFields(false);
         document.getElementById('target-primary').checked = this.settings.targets.length === 0;
         document.getElementById('start-audio-muted').checked = false;
         document.getElementById('start-video-muted').checked = false;
+        document.getElementById('target-join-mode').value = '';
         
         // Generate new code
         const code = await this.generateCode();
@@ -254,10 +413,13 @@ class SettingsManager {
         document.getElementById('target-label').value = target.label;
         document.getElementById('target-code').value = target.code;
         document.getElementById('target-notes').value = target.notes || '';
+        document.getElementById('target-channels').value = (target.channels || []).map(c => c.name).join(', ');
         document.querySelector(`input[name="target-type"][value="${target.type || target.target_type}"]`).checked = true;
+        this.updateTypeFields(false);
         document.getElementById('target-primary').checked = target.is_primary;
         document.getElementById('start-audio-muted').checked = !target.call_defaults.start_with_audio;
         document.getElementById('start-video-muted').checked = !target.call_defaults.start_with_video;
+        document.getElementById('target-join-mode').value = target.call_defaults.join_mode || '';
         
         this.showModal();
     }
@@ -270,18 +432,46 @@ class SettingsManager {
             return;
         }
         
+        const type = document.querySelector('input[name="target-type"]:checked').value;
         const targetData = {
             label,
             code: document.getElementById('target-code').value,
-            type: document.querySelector('input[name="target-type"]:checked').value,
+            type,
             is_primary: document.getElementById('target-primary').checked,
             call_defaults: {
                 start_with_audio: !document.getElementById('start-audio-muted').checked,
-                start_with_video: !document.getElementById('start-video-muted').checked
+                start_with_video: !document.getElementById('start-video-muted').checked,
+                join_mode: document.getElementById('target-join-mode').value || null
             },
-            notes: document.getElementById('target-notes').value.trim() || null
+            notes: document.getElementById('target-notes').value.trim() || null,
+            channels: type === 'link' ? [] : this.readChannels()
         };
         
+        if (window.__TAURI__ && window.__TAURI__.invoke) {
+            // Persist through the store so the welcome and settings windows can't race
+            try {
+                if (this.editingTargetId) {
+                    const existing = this.settings.targets.find(t => t.id === this.editingTargetId);
+                    await window.__TAURI__.invoke('update_target', { target: { ...existing, ...targetData } });
+                } else {
+                    await window.__TAURI__.invoke('add_target', { target: targetData });
+                }
+                await this.refreshTargets();
+                this.hideModal();
+            } catch (error) {
+                console.error('Failed to save target:', error);
+                // An edited target can be merged into the one it collides with
+                if (error && error.code === 'target_conflict' && this.editingTargetId) {
+                    if (await this.offerMerge(error.details)) {
+                        this.hideModal();
+                    }
+                    return;
+                }
+                this.showError(`Failed to save target: ${errorMessage(error)}`);
+            }
+            return;
+        }
+        
         if (this.editingTargetId) {
             // Update existing target
             const index = this.settings.targets.findIndex(t => t.id === this.editingTargetId);
@@ -312,6 +502,33 @@ class SettingsManager {
         this.renderTargets();
     }
     
+    // Channel names from the modal; existing channels keep their id (and hotkey),
+    // new names get an id from the backend
+    readChannels() {
+        const existing = this.editingTargetId
+            ? (this.settings.targets.find(t => t.id === this.editingTargetId).channels || [])
+            : [];
+        const normalize = name => name.trim().toLowerCase().split(/\s+/).join('-');
+        
+        return document.getElementById('target-channels').value
+            .split(',')
+            .map(name => name.trim())
+            .filter(name => name.length > 0)
+            .map(name => {
+                const match = existing.find(c => normalize(c.name) === normalize(name));
+                return { id: match ? match.id : '', name };
+            });
+    }
+    
+    // Reload targets (and their hotkeys) from the backend, keeping other unsaved edits
+    async refreshTargets() {
+        const fresh = await window.__TAURI__.invoke('get_settings');
+        this.settings.targets = fresh.targets;
+        this.settings.keybinds.target_hotkeys = fresh.keybinds.target_hotkeys;
+        this.settings.keybinds.channel_hotkeys = fresh.keybinds.channel_hotkeys;
+        this.renderTargets();
+    }
+    
     // Remove target
     async removeTarget(targetId) {
         console.log('Removing target:', targetId);
@@ -330,8 +547,8 @@ class SettingsManager {
                 const success = await window.__TAURI__.invoke('remove_target', { id: targetId });
                 
                 if (success) {
-                    // Reload settings from backend to get updated state
-                    await this.loadSettings();
+                    // Reload targets from backend to get updated state
+                    await this.refreshTargets();
                     console.log('Target removed successfully');
                     // Show success message without toast dependency
                     this.showTempMessage('Target removed successfully', 'success');
@@ -356,7 +573,7 @@ class SettingsManager {
             }
         } catch (error) {
             console.error('Failed to remove target:', error);
-            this.showTempMessage(`Failed to remove target: ${error}`, 'error');
+            this.showTempMessage(`Failed to remove target: ${errorMessage(error)}`, 'error');
         }
     }
     
@@ -404,8 +621,8 @@ class SettingsManager {
                         window.toast.success(`Hotkey set: ${hotkey}`);
                     } catch (error) {
                         // Failed - show error
-                        window.toast.error(`Invalid hotkey: ${error}`);
-                        input.value = this.settings.keybinds[keybind];
+                        window.toast.error(`Invalid hotkey: ${errorMessage(error)}`);
+                        input.value = this.settings.keybinds[keybind] || '';
                     }
                 } else {
                     // Development mode - just set it
@@ -437,7 +654,7 @@ class SettingsManager {
         setTimeout(() => {
             document.addEventListener('click', () => {
                 input.classList.remove('recording');
-                input.value = this.settings.keybinds[keybind];
+                input.value = this.settings.keybinds[keybind] || '';
                 document.removeEventListener('keydown', handler);
                 document.removeEventListener('keyup', keyupHandler);
             }, { once: true });
@@ -487,10 +704,12 @@ class SettingsManager {
             codeInput.focus();
             importBtn.textContent = 'Cancel';
             hint.textContent = 'Paste the code from your partner';
+            codeInput.oninput = () => this.updateCodeStrength();
         } else {
             // Cancel import mode
             codeInput.readOnly = true;
             codeInput.placeholder = '';
+            codeInput.oninput = null;
             importBtn.textContent = 'Import';
             hint.textContent = 'Share this code with your call partner';
             
@@ -506,6 +725,75 @@ class SettingsManager {
         }
     }
     
+    // Show how guessable a typed/pasted code is; saving enforces the configured minimum
+    async updateCodeStrength() {
+        const code = document.getElementById('target-code').value;
+        const hint = document.getElementById('code-hint');
+        if (this.isLinkType()) {
+            hint.textContent = 'Opened as-is when you join this target';
+            return;
+        }
+        if (!code || !(window.__TAURI__ && window.__TAURI__.invoke)) {
+            hint.textContent = 'Paste the code from your partner';
+            return;
+        }
+        
+        try {
+            const estimate = await window.__TAURI__.invoke('check_code_strength', { code });
+            hint.textContent = `Strength: ${estimate.strength} (~${Math.round(estimate.bits)} bits)`;
+        } catch (error) {
+            console.error('Failed to check code strength:', error);
+        }
+    }
+    
+    isLinkType() {
+        return document.querySelector('input[name="target-type"]:checked').value === 'link';
+    }
+    
+    // Link targets store a meeting URL instead of a pairing code and have no channels.
+    // enterUrl: the user just picked Link, so swap the generated code for an empty URL field
+    updateTypeFields(enterUrl) {
+        const isLink = this.isLinkType();
+        document.getElementById('code-label').textContent = isLink ? 'Meeting URL:' : 'Pairing Code:';
+        document.getElementById('channels-group').style.display = isLink ? 'none' : '';
+        
+        const codeInput = document.getElementById('target-code');
+        if (enterUrl && isLink && codeInput.readOnly) {
+            this.toggleImportMode();
+            codeInput.placeholder = 'https://meet.google.com/abc-defg-hij';
+        }
+        if (!codeInput.readOnly) {
+            this.updateCodeStrength();
+        }
+    }
+    
+    // A pasted meeting URL can't work as a pairing code; offer to store it as a Link target
+    async offerLinkTarget() {
+        const codeInput = document.getElementById('target-code');
+        if (codeInput.readOnly || this.isLinkType() || !(window.__TAURI__ && window.__TAURI__.invoke)) return;
+        
+        let link;
+        try {
+            link = await window.__TAURI__.invoke('parse_meeting_link', { input: codeInput.value });
+        } catch (error) {
+            return; // Not a URL - an ordinary pairing code
+        }
+        
+        const providers = { jitsi: 'Jitsi', zoom: 'Zoom', google_meet: 'Google Meet', teams: 'Teams', other: 'meeting' };
+        const room = link.jitsi_room ? ` for room "${this.escapeHtml(link.jitsi_room)}"` : '';
+        const confirmed = await this.showConfirmDialog(
+            'Meeting Link',
+            `This is a ${providers[link.provider]} link (${link.host})${room}, not a pairing code. ` +
+            `Save this target as a link that opens it directly?`,
+            'Use as Link'
+        );
+        if (confirmed) {
+            codeInput.value = link.url;
+            document.querySelector('input[name="target-type"][value="link"]').checked = true;
+            this.updateTypeFields(false);
+        }
+    }
+    
     // UI helpers
     showModal() {
         document.getElementById('target-modal').classList.add('active');
@@ -516,7 +804,7 @@ class SettingsManager {
     }
     
     // Custom confirmation dialog
-    showConfirmDialog(title, message) {
+    showConfirmDialog(title, message, confirmLabel = 'Yes, Remove') {
         return new Promise((resolve) => {
             // Create confirmation dialog
             const dialog = document.createElement('div');
@@ -526,7 +814,7 @@ class SettingsManager {
                     <h3>${title}</h3>
                     <p>${message}</p>
                     <div class="modal-footer">
-                        <button class="btn btn-primary confirm-yes">Yes, Remove</button>
+                        <button class="btn btn-primary confirm-yes">${confirmLabel}</button>
                         <button class="btn btn-secondary confirm-no">Cancel</button>
                     </div>
                 </div>
@@ -695,6 +983,7 @@ class SettingsManager {
             app_settings: {
                 autostart: false,
                 always_on_top: true,
+                auto_hangup_after_secs: 300,
                 play_join_sound: true,
                 show_notifications: true,
 
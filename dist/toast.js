@@ -59,7 +59,8 @@ class ToastManager {
         // Auto dismiss
         if (duration > 0) {
             setTimeout(() => this.dismiss(toast), duration);
-        }
+        }        
+        return toast;
     }
     
     dismiss(toast) {
@@ -3,79 +3,111 @@
 // Why: Provides secure API for frontend to interact with settings
 // Used by: settings.js (frontend), lib.rs (backend)
 
+use crate::error::AppError;
 use crate::state::AppState;
-use crate::services::global_shortcuts::ShortcutAction;
-use serde_json::Value;
-use tauri::State;
+use crate::services::conference_events::{ConferenceEvent, Participant};
+use crate::services::conference_window::{self, ConferenceCommand, ConferenceWindow};
+use crate::services::global_shortcuts::{self, ShortcutAction};
+use crate::services::tray_menu;
+use crate::services::window_manager::{WindowKind, WindowManager};
+use blink::core::{CodeStrengthEstimate, MeetingLink};
+use blink::models::settings::{AppSettings, Keybinds};
+use blink::models::{Channel, KeySource, NewTarget, Target};
+use blink::storage::bundle::{self, ImportOptions, ImportReport};
+use blink::storage::{SettingsStore, TargetConflict};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, State, WebviewWindow};
 
 #[tauri::command]
-pub async fn get_settings(state: State<'_, AppState>) -> Result<Value, String> {
+pub async fn get_settings(state: State<'_, AppState>) -> Result<Value, AppError> {
     let store = state.settings_store.lock().unwrap();
     let settings = store.settings();
     
-    serde_json::to_value(settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))
+    Ok(serde_json::to_value(settings)?)
 }
 
+/// save_settings(app_settings, keybinds)
+/// What: Saves the settings page's preferences and hotkeys
+/// Contract: Targets, code encryption and the Recent list stay as the store has
+///           them (targets are edited through add_target/update_target/
+///           remove_target); a locked store is left untouched
 #[tauri::command]
 pub async fn save_settings(
-    settings: Value,
+    app_settings: AppSettings,
+    keybinds: Keybinds,
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    // First, update hotkeys if they changed
-    let old_keybinds = {
+) -> Result<(), AppError> {
+    let (old_keybinds, old_prewarm) = {
         let store = state.settings_store.lock().unwrap();
-        store.settings().keybinds.clone()
+        let settings = store.settings();
+        (settings.keybinds.clone(), settings.app_settings.prewarm_conference_window)
     };
+    let prewarm = app_settings.prewarm_conference_window;
+    let keybinds_changed = old_keybinds != keybinds;
     
-    // Deserialize the new settings
-    let new_settings: blink::models::Settings = serde_json::from_value(settings)
-        .map_err(|e| format!("Invalid settings format: {}", e))?;
+    state.settings_store.lock().unwrap()
+        .update_preferences(app_settings, keybinds.clone())
+        .map_err(|e| AppError::settings("Failed to save settings", e))?;
     
-    // Update hotkeys if changed
-    if old_keybinds != new_settings.keybinds {
+    // Update hotkeys if changed (failures are logged; the settings are saved)
+    if keybinds_changed {
         log::info!("Hotkeys changed, updating global shortcuts");
-        
-        let mut shortcuts = state.shortcuts.lock().unwrap();
-        
-        // Unregister old hotkeys
-        if let Err(e) = shortcuts.unregister_all() {
-            log::error!("Failed to unregister old hotkeys: {}", e);
-        }
-        
-        // Register new hotkeys
-        if let Err(e) = shortcuts.setup_default_hotkeys(&new_settings.keybinds) {
-            log::error!("Failed to setup new hotkeys: {}", e);
-            // Continue anyway - settings should still be saved
-        }
+        reload_hotkeys(&state, &keybinds);
     }
+    tray_menu::refresh(&app);
     
-    // Update the store
-    let mut store = state.settings_store.lock().unwrap();
-    *store.settings_mut() = new_settings;
-    
-    // Save to disk
-    store.save()
-        .map_err(|e| format!("Failed to save settings: {}", e))
+    if prewarm != old_prewarm {
+        if prewarm {
+            // Built without the window lock (see ConferenceWindow::prewarm)
+            if let Err(e) = ConferenceWindow::prewarm(&app) {
+                log::error!("Failed to pre-warm conference window: {}", e);
+            }
+        } else {
+            state.conference_window.lock().unwrap().discard_prewarmed();
+        }
+    }
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn generate_code() -> Result<String, String> {
+pub async fn generate_code() -> Result<String, AppError> {
     Ok(blink::core::crypto::generate_code_base32_100b())
 }
 
+/// Estimate a custom pairing code's strength for the code field hint
+#[tauri::command]
+pub async fn check_code_strength(code: String) -> Result<CodeStrengthEstimate, AppError> {
+    Ok(blink::core::estimate_code_strength(&code))
+}
+
+/// Recognize a pasted meeting URL so the settings UI can offer a Link target
+#[tauri::command]
+pub async fn parse_meeting_link(input: String) -> Result<MeetingLink, AppError> {
+    blink::core::parse_meeting_link(&input).map_err(|e| AppError::Validation { message: e.to_string() })
+}
+
 #[tauri::command]
-pub async fn validate_hotkey(hotkey: String, state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn validate_hotkey(hotkey: String, state: State<'_, AppState>) -> Result<bool, AppError> {
+    // Malformed hotkeys are an error, not just "unavailable"
+    global_shortcuts::parse_hotkey(&hotkey)?;
+    
     // Check if hotkey is already in use
     let shortcuts = state.shortcuts.lock().unwrap();
     Ok(!shortcuts.is_registered(&hotkey))
 }
 
 #[tauri::command]
-pub async fn test_hotkey(hotkey: String, state: State<'_, AppState>) -> Result<(), String> {
+pub async fn test_hotkey(hotkey: String, state: State<'_, AppState>) -> Result<(), AppError> {
     // Temporarily register a hotkey to test if it works
     let mut shortcuts = state.shortcuts.lock().unwrap();
     
+    // Already live - registering and unregistering would drop the real binding
+    if shortcuts.is_registered(&hotkey) {
+        return Ok(());
+    }
+    
     // Try to register
     shortcuts.register_hotkey(&hotkey, ShortcutAction::JoinPrimary)?;
     
@@ -86,9 +118,309 @@ pub async fn test_hotkey(hotkey: String, state: State<'_, AppState>) -> Result<(
 }
 
 #[tauri::command]
-pub async fn remove_target(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+pub async fn remove_target(id: String, app: AppHandle, state: State<'_, AppState>) -> Result<bool, AppError> {
+    update_targets(&app, &state, |store| {
+        store.remove_target(&id)
+            .map_err(|e| AppError::settings("Failed to remove target", e))
+    })
+}
+
+#[tauri::command]
+pub async fn get_target(id: String, state: State<'_, AppState>) -> Result<Target, AppError> {
+    let store = state.settings_store.lock().unwrap();
+    store.get_target(&id).cloned().ok_or(AppError::TargetNotFound { id })
+}
+
+/// Add a target; the store generates id, created_at and (if missing) the code
+#[tauri::command]
+pub async fn add_target(target: NewTarget, app: AppHandle, state: State<'_, AppState>) -> Result<Target, AppError> {
+    update_targets(&app, &state, |store| {
+        store.create_target(target)
+            .map_err(|e| AppError::settings("Failed to add target", e))
+    })
+}
+
+#[tauri::command]
+pub async fn update_target(target: Target, app: AppHandle, state: State<'_, AppState>) -> Result<Target, AppError> {
+    update_targets(&app, &state, |store| {
+        let id = target.id.clone();
+        
+        let updated = store.update_target(target)
+            .map_err(|e| AppError::settings("Failed to update target", e))?;
+        if !updated {
+            return Err(AppError::TargetNotFound { id });
+        }
+        store.get_target(&id).cloned().ok_or(AppError::TargetNotFound { id })
+    })
+}
+
+#[tauri::command]
+pub async fn set_primary_target(id: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), AppError> {
+    update_targets(&app, &state, |store| {
+        let found = store.set_primary_target(&id)
+            .map_err(|e| AppError::settings("Failed to set primary target", e))?;
+        if found { Ok(()) } else { Err(AppError::TargetNotFound { id }) }
+    })
+}
+
+#[tauri::command]
+pub async fn reorder_targets(ids: Vec<String>, app: AppHandle, state: State<'_, AppState>) -> Result<(), AppError> {
+    update_targets(&app, &state, |store| {
+        store.reorder_targets(&ids)
+            .map_err(|e| AppError::settings("Failed to reorder targets", e))
+    })
+}
+
+/// Targets sharing a pairing code or derived room (loaded from older/hand-edited files)
+#[tauri::command]
+pub async fn get_target_conflicts(state: State<'_, AppState>) -> Result<Vec<TargetConflict>, AppError> {
+    let store = state.settings_store.lock().unwrap();
+    Ok(store.conflicts())
+}
+
+/// Merge remove_id into keep_id; returns the merged target
+#[tauri::command]
+pub async fn merge_targets(
+    keep_id: String,
+    remove_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Target, AppError> {
+    update_targets(&app, &state, |store| {
+        store.merge_targets(&keep_id, &remove_id)
+            .map_err(|e| AppError::settings("Failed to merge targets", e))
+    })
+}
+
+/// Add a named channel (sub-room) to a target
+#[tauri::command]
+pub async fn add_channel(
+    target_id: String,
+    name: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Channel, AppError> {
+    update_targets(&app, &state, |store| {
+        if store.get_target(&target_id).is_none() {
+            return Err(AppError::TargetNotFound { id: target_id });
+        }
+        store.add_channel(&target_id, &name)
+            .map_err(|e| AppError::settings("Failed to add channel", e))
+    })
+}
+
+/// Remove a channel and its hotkey; false if the target has no such channel
+#[tauri::command]
+pub async fn remove_channel(
+    target_id: String,
+    channel_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<bool, AppError> {
+    update_targets(&app, &state, |store| {
+        store.remove_channel(&target_id, &channel_id)
+            .map_err(|e| AppError::settings("Failed to remove channel", e))
+    })
+}
+
+#[tauri::command]
+pub async fn export_settings(
+    path: String,
+    include_settings: bool,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    let store = state.settings_store.lock().unwrap();
+    
+    store.export_to_path(&path, include_settings, passphrase.as_deref().filter(|p| !p.is_empty()))
+        .map_err(|e| AppError::settings("Failed to export settings", e))
+}
+
+#[tauri::command]
+pub async fn preview_import(
+    path: String,
+    passphrase: Option<String>,
+    options: ImportOptions,
+    state: State<'_, AppState>,
+) -> Result<ImportReport, AppError> {
+    let bundle = bundle::read_bundle(&PathBuf::from(path), passphrase.as_deref())
+        .map_err(|e| AppError::settings("Failed to read bundle", e))?;
+    
+    let store = state.settings_store.lock().unwrap();
+    Ok(store.preview_import(&bundle, &options))
+}
+
+#[tauri::command]
+pub async fn import_settings(
+    path: String,
+    passphrase: Option<String>,
+    options: ImportOptions,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ImportReport, AppError> {
+    let bundle = bundle::read_bundle(&PathBuf::from(path), passphrase.as_deref())
+        .map_err(|e| AppError::settings("Failed to read bundle", e))?;
+    
+    // Imported keybinds take effect immediately, same as save_settings
+    update_targets(&app, &state, |store| {
+        store.import_bundle(&bundle, &options)
+            .map_err(|e| AppError::settings("Failed to import settings", e))
+    })
+}
+
+#[tauri::command]
+pub async fn get_encryption_status(state: State<'_, AppState>) -> Result<Value, AppError> {
+    let store = state.settings_store.lock().unwrap();
+    let method = store.settings().code_encryption.as_ref().map(|info| info.method);
+    
+    Ok(json!({
+        "locked": store.is_locked(),
+        "encrypted": method.is_some(),
+        "method": method,
+    }))
+}
+
+#[tauri::command]
+pub async fn unlock_settings(passphrase: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), AppError> {
+    let path = state.settings_store.lock().unwrap().file_path().to_path_buf();
+    
+    let unlocked = SettingsStore::load_from_path_with_passphrase(&path, &passphrase)
+        .map_err(|e| AppError::settings("Failed to unlock settings", e))?;
+    let keybinds = unlocked.settings().keybinds.clone();
+    *state.settings_store.lock().unwrap() = unlocked;
+    
+    // Startup registered hotkeys from defaults; switch to the real ones
+    reload_hotkeys(&state, &keybinds);
+    tray_menu::refresh(&app);
+    
+    log::info!("Settings unlocked");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_code_encryption(
+    method: Option<KeySource>,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
     let mut store = state.settings_store.lock().unwrap();
     
-    store.remove_target(&id)
-        .map_err(|e| format!("Failed to remove target: {}", e))
+    match method {
+        Some(method) => store.enable_code_encryption(method, passphrase.as_deref()),
+        None => store.disable_code_encryption(),
+    }
+    .map_err(|e| AppError::settings("Failed to update code encryption", e))
+}
+
+/// Open (or focus) one of the app's windows
+/// Contract: Settings and welcome are never open together - the open one is focused
+#[tauri::command]
+pub async fn open_window(kind: WindowKind, app: AppHandle) -> Result<(), AppError> {
+    WindowManager::show(&app, kind).map(|_| ())
+}
+
+/// Bridge for events from conference.html (Jitsi iframe API)
+/// What: Validates the event into a ConferenceEvent, feeds CallController and
+///       re-emits it app-wide as "conference-event"; dom-ready goes back to the
+///       conference window's own listener (ConferenceWindow shows the window);
+///       readyToClose also closes the window
+/// Contract:
+///   - Only the conference window may call this, and only about itself
+///   - Unknown events and malformed payloads are Validation errors
+#[tauri::command]
+pub async fn emit_to_window(
+    window: String,
+    event: String,
+    payload: Option<Value>,
+    webview_window: WebviewWindow,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    if webview_window.label() != conference_window::WINDOW_LABEL || window != webview_window.label() {
+        return Err(AppError::Validation {
+            message: format!("Window '{}' may not send conference events", webview_window.label()),
+        });
+    }
+    
+    let event = ConferenceEvent::parse(&event, payload)?;
+    if event == ConferenceEvent::DomReady {
+        return webview_window.emit_to(webview_window.label(), "dom-ready", ())
+            .map_err(|e| AppError::Window { label: window, message: e.to_string() });
+    }
+    
+    if event == ConferenceEvent::ReadyToClose {
+        // Jitsi's own hangup button: end the call and close the window too
+        let mut window = state.conference_window.lock().unwrap();
+        state.call_controller.lock().unwrap().handle_event(&event);
+        window.close();
+    } else {
+        state.call_controller.lock().unwrap().handle_event(&event);
+    }
+    if let Err(e) = app.emit("conference-event", &event) {
+        log::error!("Failed to emit conference event: {}", e);
+    }
+    Ok(())
+}
+
+/// Other participants in the current embedded call
+/// Used by: conference/settings UI (kept current via "participants-changed")
+#[tauri::command]
+pub async fn get_participants(state: State<'_, AppState>) -> Result<Vec<Participant>, AppError> {
+    Ok(state.call_controller.lock().unwrap().participants())
+}
+
+/// Restart the auto-hangup countdown ("Stay" on the alone warning)
+#[tauri::command]
+pub async fn postpone_auto_hangup(state: State<'_, AppState>) -> Result<(), AppError> {
+    state.call_controller.lock().unwrap().postpone_auto_hangup();
+    Ok(())
+}
+
+/// Run a Jitsi command (mute, screen share, raise hand...) in the embedded call
+/// Contract: Validation error for bad display names/subjects; no-op without a call
+#[tauri::command]
+pub async fn send_conference_command(command: ConferenceCommand, state: State<'_, AppState>) -> Result<(), AppError> {
+    // Same lock order as the hotkey handlers: window, then controller
+    let mut window = state.conference_window.lock().unwrap();
+    let controller = state.call_controller.lock().unwrap();
+    controller.send_command(&mut window, command)
+}
+
+/// update_targets(app, state, change)
+/// What: Runs a store change, then re-registers hotkeys (if keybinds changed)
+///       and rebuilds the tray menu
+/// Why: Removing/merging targets or channels drops their hotkeys, and the tray
+///      Join menu lists targets and channels
+/// Used by: target, channel and import commands
+/// Contract: A failed change (locked store included) skips both, so nothing
+///           unsaved becomes joinable
+fn update_targets<T>(
+    app: &AppHandle,
+    state: &AppState,
+    change: impl FnOnce(&mut SettingsStore) -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    let (value, old_keybinds, new_keybinds) = {
+        let mut store = state.settings_store.lock().unwrap();
+        let old_keybinds = store.settings().keybinds.clone();
+        let value = change(&mut store)?;
+        (value, old_keybinds, store.settings().keybinds.clone())
+    };
+    
+    if old_keybinds != new_keybinds {
+        log::info!("Hotkeys changed, updating global shortcuts");
+        reload_hotkeys(state, &new_keybinds);
+    }
+    tray_menu::refresh(app);
+    Ok(value)
+}
+
+/// Unregister everything and register keybinds; failures are logged
+fn reload_hotkeys(state: &AppState, keybinds: &Keybinds) {
+    let mut shortcuts = state.shortcuts.lock().unwrap();
+    if let Err(e) = shortcuts.unregister_all() {
+        log::error!("Failed to unregister old hotkeys: {}", e);
+    }
+    if let Err(e) = shortcuts.setup_default_hotkeys(keybinds) {
+        log::error!("Failed to setup new hotkeys: {}", e);
+    }
 }
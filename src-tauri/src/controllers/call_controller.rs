@@ -1,19 +1,113 @@
-/// Call Controller (Simplified)
-/// What: Manages call lifecycle without complex state machine
+/// Call Controller
+/// What: Routes joins to the conference window or the system browser and
+///       tracks the embedded call's state
 /// Why: Provides clean separation between hotkeys and window management
-/// Used by: Hotkey handlers in lib.rs
-/// Note: This is a simplified version after the full controller was deleted
+/// Used by: Hotkey/tray handlers in lib.rs, commands::emit_to_window (conference events)
+/// Events: Emits "call-state-changed" (CallState) on every transition,
+///         "join-latency" ({millis, prewarmed}, join to conference joined),
+///         "participants-changed" (Roster), "mute-changed" (bool) and
+///         "alone-warning" (to the conference window)
+/// Change notes: Only embedded calls are tracked - a browser tab is invisible to us
 
-use crate::services::conference_window::{ConferenceWindow, ConferenceConfig};
-use blink::core::CallState;
+use crate::error::AppError;
+use crate::services::conference_events::{ConferenceEvent, Participant, Roster};
+use crate::services::conference_window::{self, ConferenceCommand, ConferenceWindow, ConferenceConfig};
+use crate::services::external_browser::ExternalBrowserService;
+use crate::services::notifier::{self, Notification};
+use crate::services::window_manager::{WindowKind, WindowManager};
+use crate::state::AppState;
+use blink::core::{channel_room_id, meeting_url, room_id_from_code, CallState};
+use blink::models::{BrowserSettings, Channel, JoinMode, Settings, Target};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
-use tauri::AppHandle;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How long before an auto-hangup the conference window warns
+const AUTO_HANGUP_WARNING: Duration = Duration::from_secs(30);
+
+/// Where a join request goes
+#[derive(Debug, Clone)]
+pub enum JoinPath {
+    /// Conference window (Jitsi iframe API); hangup/mute hotkeys work
+    Embedded {
+        config: ConferenceConfig,
+        /// Time alone in the room before hanging up (None = never)
+        auto_hangup_after: Option<Duration>,
+    },
+    /// Browser (app_settings.browser or the system default); the app can't
+    /// see or control the call
+    External { url: String, browser: BrowserSettings },
+}
+
+/// join_path(settings, target, channel)
+/// What: Resolves the join mode and builds what that mode needs
+/// Used by: lib.rs join handlers
+/// Calls: Settings::join_mode_for()
+pub fn join_path(settings: &Settings, target: &Target, channel: Option<&Channel>) -> JoinPath {
+    let room_id = match channel {
+        Some(channel) => channel_room_id(target.code.expose(), &channel.name),
+        None => room_id_from_code(target.code.expose()),
+    };
+
+    match settings.join_mode_for(target) {
+        JoinMode::Embedded => JoinPath::Embedded {
+            config: ConferenceConfig {
+                room_id,
+                display_name: target.call_defaults.display_name.clone().unwrap_or_else(|| "You".to_string()),
+                start_with_audio_muted: !target.call_defaults.start_with_audio,
+                start_with_video_muted: !target.call_defaults.start_with_video,
+                always_on_top: settings.app_settings.always_on_top,
+                target_id: target.id.clone(),
+                geometry: target.call_defaults.window.clone(),
+            },
+            auto_hangup_after: match settings.app_settings.auto_hangup_after_secs {
+                0 => None,
+                secs => Some(Duration::from_secs(secs)),
+            },
+        },
+        JoinMode::External => JoinPath::External {
+            url: match channel {
+                Some(_) => meeting_url(&room_id),
+                None => target.meeting_url(),
+            },
+            browser: settings.app_settings.browser.clone(),
+        },
+    }
+}
+
+/// alone_schedule(timeout)
+/// What: (wait before warning, warning length) for an auto-hangup after `timeout`
+/// Contract: Timeouts shorter than AUTO_HANGUP_WARNING warn right away
+pub fn alone_schedule(timeout: Duration) -> (Duration, Duration) {
+    let warning = timeout.min(AUTO_HANGUP_WARNING);
+    (timeout - warning, warning)
+}
 
 pub struct CallController {
-    /// Current call state
+    /// Current call state (embedded calls only)
     state: Mutex<CallState>,
-    
-    /// Handle to emit events
+
+    /// Target of the current embedded call
+    target_id: Mutex<Option<String>>,
+
+    /// Other participants in the current embedded call
+    roster: Mutex<Roster>,
+
+    /// Our microphone is muted in the current embedded call
+    muted: Mutex<bool>,
+
+    /// Auto-hangup timeout of the current call
+    auto_hangup_after: Mutex<Option<Duration>>,
+
+    /// When the current join started and whether the window was pre-warmed
+    join_started: Mutex<Option<(Instant, bool)>>,
+
+    /// Bumped whenever the alone timer is (re)armed or disarmed; a timer
+    /// thread only acts if the generation it was started with is still current
+    alone_generation: AtomicU64,
+
+    /// Handle to emit events and open the browser
     app_handle: AppHandle,
 }
 
@@ -22,32 +116,319 @@ impl CallController {
     pub fn new(app_handle: AppHandle) -> Self {
         Self {
             state: Mutex::new(CallState::Idle),
+            target_id: Mutex::new(None),
+            roster: Mutex::new(Roster::default()),
+            muted: Mutex::new(false),
+            auto_hangup_after: Mutex::new(None),
+            join_started: Mutex::new(None),
+            alone_generation: AtomicU64::new(0),
             app_handle,
         }
     }
-    
-    /// Join a call - simplified version
-    /// Just opens the window without complex state checks
-    pub fn join(&self, _target_id: String, window: &mut ConferenceWindow, config: ConferenceConfig) -> Result<(), String> {
-        // For now, just open the window
-        window.open(config)
+
+    /// Current call state
+    pub fn state(&self) -> CallState {
+        *self.state.lock().unwrap()
+    }
+
+    /// Target of the current embedded call, if any
+    pub fn target_id(&self) -> Option<String> {
+        self.target_id.lock().unwrap().clone()
+    }
+
+    /// Whether our microphone is muted in the current embedded call
+    pub fn is_muted(&self) -> bool {
+        *self.muted.lock().unwrap()
     }
-    
-    /// Hangup - simplified version
-    pub fn hangup(&self, window: &mut ConferenceWindow) -> Result<(), String> {
+
+    /// Other participants in the current embedded call
+    pub fn participants(&self) -> Vec<Participant> {
+        self.roster.lock().unwrap().participants().to_vec()
+    }
+
+    /// Join a call
+    /// What: Opens the conference window or the browser, per JoinPath
+    /// Contract:
+    ///   - Embedded: Idle -> Connecting; window failures go back to Idle
+    ///   - Embedded while already in a call with this target: focuses the window
+    ///   - Embedded while in a call with another target: Validation error
+    ///     (hang up first), so a hotkey never silently drops a call
+    ///   - External: opens the browser, state is untouched
+    pub fn join(&self, target_id: String, path: JoinPath, window: &mut ConferenceWindow) -> Result<(), AppError> {
+        let (config, auto_hangup_after) = match path {
+            JoinPath::External { url, browser } => {
+                return ExternalBrowserService::open_meeting(&self.app_handle, &url, &browser);
+            }
+            JoinPath::Embedded { config, auto_hangup_after } => (config, auto_hangup_after),
+        };
+
+        if self.state().is_busy() {
+            if self.target_id().as_deref() == Some(target_id.as_str()) {
+                WindowManager::focus_existing(&self.app_handle, WindowKind::Conference);
+                return Ok(());
+            }
+            return Err(AppError::Validation {
+                message: format!("Already {} - hang up before joining another call", self.state()),
+            });
+        }
+
+        self.transition_to(CallState::Connecting);
+        *self.join_started.lock().unwrap() = Some((Instant::now(), window.is_warm()));
+        *self.target_id.lock().unwrap() = Some(target_id);
+        *self.auto_hangup_after.lock().unwrap() = auto_hangup_after;
+        // Jitsi only reports later changes, not the initial state
+        self.set_muted(config.start_with_audio_muted);
+        if let Err(e) = window.open(config) {
+            self.reset();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Hang up
+    /// What: Ends the embedded call and closes its window
+    /// Contract: No-op (logged) when there's no embedded call - browser tabs
+    ///           have to be closed by the user
+    pub fn hangup(&self, window: &mut ConferenceWindow) -> Result<(), AppError> {
+        if !self.state().is_busy() {
+            log::info!("Hangup ignored: no call in the conference window");
+            return Ok(());
+        }
+
+        self.transition_to(CallState::Disconnecting);
         window.close();
+        self.reset();
         Ok(())
     }
-    
+
+    /// Toggle the microphone in the embedded call
+    pub fn toggle_mute(&self, window: &mut ConferenceWindow) -> Result<(), AppError> {
+        self.send_command(window, ConferenceCommand::ToggleMute)
+    }
+
+    /// Toggle the camera in the embedded call
+    pub fn toggle_video(&self, window: &mut ConferenceWindow) -> Result<(), AppError> {
+        self.send_command(window, ConferenceCommand::ToggleVideo)
+    }
+
+    /// Run a command in the embedded call
+    /// Used by: toggle_mute/toggle_video, send_conference_command
+    /// Contract:
+    ///   - Hangup goes through hangup() so the window closes and state resets
+    ///   - No-op (logged) when there's no embedded call
+    pub fn send_command(&self, window: &mut ConferenceWindow, command: ConferenceCommand) -> Result<(), AppError> {
+        if command == ConferenceCommand::Hangup {
+            return self.hangup(window);
+        }
+        if !self.state().is_busy() {
+            log::info!("Ignoring {:?}: no call in the conference window", command);
+            return Ok(());
+        }
+        window.send_command(&command)
+    }
+
+    /// Handle a validated event from the conference page
+    /// Used by: commands::emit_to_window
+    pub fn handle_event(&self, event: &ConferenceEvent) {
+        match event {
+            ConferenceEvent::VideoConferenceJoined { .. } => self.on_conference_joined(),
+            ConferenceEvent::VideoConferenceLeft { .. } | ConferenceEvent::ReadyToClose => self.on_conference_left(),
+            ConferenceEvent::ParticipantJoined(_) | ConferenceEvent::ParticipantLeft { .. } => {
+                self.on_participants_event(event);
+            }
+            ConferenceEvent::AudioMuteStatusChanged { muted } => self.set_muted(*muted),
+            ConferenceEvent::DomReady => {}
+        }
+    }
+
+    /// Record the microphone state; emits "mute-changed" when it changes
+    fn set_muted(&self, muted: bool) {
+        let mut current = self.muted.lock().unwrap();
+        if *current == muted {
+            return;
+        }
+        *current = muted;
+        drop(current);
+
+        log::info!("Microphone {}", if muted { "muted" } else { "unmuted" });
+        if let Err(e) = self.app_handle.emit("mute-changed", muted) {
+            log::error!("Failed to emit mute state: {}", e);
+        }
+    }
+
+    /// Keep the roster current; arm the alone timer when the last one leaves
+    /// and notify (PartnerJoined) when someone new arrives
+    fn on_participants_event(&self, event: &ConferenceEvent) {
+        let mut roster = self.roster.lock().unwrap();
+        let newcomer = match event {
+            ConferenceEvent::ParticipantJoined(joined)
+                if !roster.participants().iter().any(|p| p.id == joined.id) => Some(joined.clone()),
+            _ => None,
+        };
+        if !roster.apply(event) {
+            return;
+        }
+        if let Some(joined) = newcomer {
+            notifier::notify(&self.app_handle, Notification::partner_joined(joined.display_name.as_deref()));
+        }
+        let alone = roster.is_empty();
+        log::info!("Participants: {}", roster.participants().len());
+        if let Err(e) = self.app_handle.emit("participants-changed", &*roster) {
+            log::error!("Failed to emit participants: {}", e);
+        }
+        drop(roster);
+
+        if alone && self.state() == CallState::InCall {
+            self.arm_alone_timer();
+        } else if !alone {
+            self.disarm_alone_timer();
+        }
+    }
+
+    /// Restart the alone countdown ("Stay" on the warning toast)
+    /// Used by: postpone_auto_hangup command
+    pub fn postpone_auto_hangup(&self) {
+        if self.state() == CallState::InCall && self.roster.lock().unwrap().is_empty() {
+            log::info!("Auto-hangup postponed");
+            self.arm_alone_timer();
+        }
+    }
+
+    /// Start (or restart) the countdown to hanging up an empty call
+    /// What: Warns the conference window, then hangs up, unless the generation moved on
+    fn arm_alone_timer(&self) {
+        let Some(timeout) = *self.auto_hangup_after.lock().unwrap() else {
+            return;
+        };
+        let generation = self.alone_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let (wait, warning) = alone_schedule(timeout);
+        let app_handle = self.app_handle.clone();
+
+        std::thread::spawn(move || {
+            let still_alone = |app_handle: &AppHandle| {
+                let state = app_handle.state::<AppState>();
+                let controller = state.call_controller.lock().unwrap();
+                controller.alone_generation.load(Ordering::SeqCst) == generation
+            };
+
+            std::thread::sleep(wait);
+            if !still_alone(&app_handle) {
+                return;
+            }
+            let payload = serde_json::json!({ "seconds": warning.as_secs() });
+            if let Err(e) = app_handle.emit_to(conference_window::WINDOW_LABEL, "alone-warning", payload) {
+                log::error!("Failed to send auto-hangup warning: {}", e);
+            }
+
+            std::thread::sleep(warning);
+            // Same lock order as the hotkey handlers: window, then controller
+            let state = app_handle.state::<AppState>();
+            let mut window = state.conference_window.lock().unwrap();
+            let controller = state.call_controller.lock().unwrap();
+            if controller.alone_generation.load(Ordering::SeqCst) == generation {
+                log::info!("Alone for {}s, hanging up", timeout.as_secs());
+                if let Err(e) = controller.hangup(&mut window) {
+                    log::error!("Auto-hangup failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Cancel a pending auto-hangup
+    fn disarm_alone_timer(&self) {
+        self.alone_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
     /// Handle conference joined event
     pub fn on_conference_joined(&self) {
-        // Simplified - just log
         log::info!("Conference joined");
+        if let Some((started, prewarmed)) = self.join_started.lock().unwrap().take() {
+            let millis = started.elapsed().as_millis() as u64;
+            log::info!("Join latency: {} ms (pre-warmed window: {})", millis, prewarmed);
+            let payload = serde_json::json!({ "millis": millis, "prewarmed": prewarmed });
+            if let Err(e) = self.app_handle.emit("join-latency", payload) {
+                log::error!("Failed to emit join latency: {}", e);
+            }
+        }
+        if self.transition_to(CallState::InCall) && self.roster.lock().unwrap().is_empty() {
+            self.arm_alone_timer();
+        }
     }
-    
+
     /// Handle conference left event
+    /// What: The page left the meeting (Jitsi hangup button, kicked, ...)
     pub fn on_conference_left(&self) {
-        // Simplified - just log
         log::info!("Conference left");
+        if self.state().is_busy() {
+            self.transition_to(CallState::Disconnecting);
+            self.reset();
+        }
+    }
+
+    /// Handle the conference window being destroyed
+    /// What: Title-bar X, OS close or our own close(): the call is over
+    /// Used by: ConferenceWindow destroy handler
+    /// Contract: No-op after hangup() (already Idle)
+    pub fn on_window_closed(&self) {
+        if self.state().is_busy() {
+            log::info!("Conference window closed during the call");
+            self.transition_to(CallState::Disconnecting);
+            self.reset();
+        }
+    }
+
+    /// Move to `next` if the state machine allows it; returns whether it did
+    fn transition_to(&self, next: CallState) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.can_transition_to(next) {
+            log::warn!("Ignoring call state change {} -> {}", *state, next);
+            return false;
+        }
+        log::info!("Call state: {} -> {}", *state, next);
+        *state = next;
+        drop(state);
+
+        if let Err(e) = self.app_handle.emit("call-state-changed", next) {
+            log::error!("Failed to emit call state: {}", e);
+        }
+        true
     }
-}
\ No newline at end of file
+
+    /// Back to Idle from Disconnecting (or a failed Connecting)
+    fn reset(&self) {
+        if self.state() == CallState::Connecting {
+            self.transition_to(CallState::Disconnecting);
+        }
+        self.transition_to(CallState::Idle);
+        *self.target_id.lock().unwrap() = None;
+        *self.join_started.lock().unwrap() = None;
+        *self.auto_hangup_after.lock().unwrap() = None;
+        self.set_muted(false);
+        self.disarm_alone_timer();
+
+        let mut roster = self.roster.lock().unwrap();
+        if !roster.is_empty() {
+            roster.clear();
+            if let Err(e) = self.app_handle.emit("participants-changed", &*roster) {
+                log::error!("Failed to emit participants: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alone_schedule() {
+        assert_eq!(alone_schedule(Duration::from_secs(300)), (Duration::from_secs(270), AUTO_HANGUP_WARNING));
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_alone_schedule_short_timeout() {
+        assert_eq!(alone_schedule(Duration::from_secs(10)), (Duration::ZERO, Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,218 @@
+// Application errors
+// What: Typed errors for commands and services, serialized as {code, message, details}
+// Why: The frontend needs to branch on what went wrong (bad hotkey vs disk error),
+//      not parse English strings
+// Used by: commands.rs, GlobalShortcutService, ConferenceWindow, ExternalBrowserService,
+//          Notifier
+// Change notes: `code` strings are API for the frontend - add new ones, don't rename
+
+use blink::storage::{SettingsLocked, TargetConflict};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Error returned by every Tauri command
+/// What: One variant per failure family the UI handles differently
+/// Why: Replaces Result<_, String>
+/// Contract:
+///   - Serializes to {"code": "...", "message": "...", "details": {...}}
+///   - message is human readable and safe to show; details carries the fields
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// Reading/writing settings (or a bundle/keyfile) failed
+    SettingsIo { message: String },
+    /// A file or payload wasn't valid JSON / didn't match the schema
+    SettingsParse { message: String },
+    /// Settings are passphrase-protected and not unlocked yet
+    SettingsLocked,
+    /// Input was well-formed but not acceptable (unknown target, wrong passphrase, ...)
+    Validation { message: String },
+    /// No target with this id
+    TargetNotFound { id: String },
+    /// Target would join the same room as another one (details offer a merge)
+    TargetConflict(TargetConflict),
+    /// Hotkey string could not be parsed
+    HotkeyParse { hotkey: String, message: String },
+    /// Hotkey is already bound, by Blink or by another application
+    HotkeyConflict { hotkey: String, message: String },
+    /// The OS refused to register/unregister a hotkey for another reason
+    HotkeyRegistration { hotkey: String, message: String },
+    /// Creating or talking to a window failed
+    Window { label: String, message: String },
+    /// The meeting provider/browser could not be opened
+    Provider { message: String },
+    /// A desktop notification could not be shown
+    Notification { message: String },
+}
+
+impl AppError {
+    /// Stable machine-readable code for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::SettingsIo { .. } => "settings_io",
+            AppError::SettingsParse { .. } => "settings_parse",
+            AppError::SettingsLocked => "settings_locked",
+            AppError::Validation { .. } => "validation",
+            AppError::TargetNotFound { .. } => "target_not_found",
+            AppError::TargetConflict(_) => "target_conflict",
+            AppError::HotkeyParse { .. } => "hotkey_parse",
+            AppError::HotkeyConflict { .. } => "hotkey_conflict",
+            AppError::HotkeyRegistration { .. } => "hotkey_registration",
+            AppError::Window { .. } => "window",
+            AppError::Provider { .. } => "provider",
+            AppError::Notification { .. } => "notification",
+        }
+    }
+
+    /// Structured fields besides the message
+    pub fn details(&self) -> Value {
+        match self {
+            AppError::HotkeyParse { hotkey, .. }
+            | AppError::HotkeyConflict { hotkey, .. }
+            | AppError::HotkeyRegistration { hotkey, .. } => json!({ "hotkey": hotkey }),
+            AppError::Window { label, .. } => json!({ "label": label }),
+            AppError::TargetNotFound { id } => json!({ "id": id }),
+            AppError::TargetConflict(conflict) => json!(conflict),
+            _ => Value::Null,
+        }
+    }
+
+    /// settings(context, error)
+    /// What: Classifies an anyhow error from SettingsStore/bundle code
+    /// Why: The library reports with anyhow; the cause chain tells us the family
+    /// Contract:
+    ///   - SettingsLocked -> SettingsLocked
+    ///   - TargetConflict -> TargetConflict (message without the context prefix)
+    ///   - io::Error anywhere in the chain -> SettingsIo
+    ///   - serde_json::Error anywhere in the chain -> SettingsParse
+    ///   - anything else -> Validation
+    pub fn settings(context: &str, error: anyhow::Error) -> Self {
+        if error.downcast_ref::<SettingsLocked>().is_some() {
+            return AppError::SettingsLocked;
+        }
+        if let Some(conflict) = error.downcast_ref::<TargetConflict>() {
+            return AppError::TargetConflict(conflict.clone());
+        }
+
+        let message = format!("{}: {:#}", context, error);
+        if error.chain().any(|cause| cause.is::<std::io::Error>()) {
+            AppError::SettingsIo { message }
+        } else if error.chain().any(|cause| cause.is::<serde_json::Error>()) {
+            AppError::SettingsParse { message }
+        } else {
+            AppError::Validation { message }
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::SettingsLocked => write!(f, "{}", SettingsLocked),
+            AppError::SettingsIo { message }
+            | AppError::SettingsParse { message }
+            | AppError::Validation { message }
+            | AppError::Provider { message }
+            | AppError::Notification { message } => f.write_str(message),
+            AppError::TargetNotFound { id } => write!(f, "No target with id '{}'", id),
+            AppError::TargetConflict(conflict) => write!(f, "{}", conflict),
+            AppError::HotkeyParse { hotkey, message } => {
+                write!(f, "Invalid hotkey format '{}': {}", hotkey, message)
+            }
+            AppError::HotkeyConflict { hotkey, message } => {
+                write!(f, "Hotkey '{}' is already in use: {}", hotkey, message)
+            }
+            AppError::HotkeyRegistration { hotkey, message } => {
+                write!(f, "Failed to register hotkey '{}': {}", hotkey, message)
+            }
+            AppError::Window { label, message } => write!(f, "Window '{}': {}", label, message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::SettingsParse { message: format!("Invalid settings format: {}", error) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_serialized_shape() {
+        let error = AppError::HotkeyConflict {
+            hotkey: "Ctrl+Alt+J".to_string(),
+            message: "bound to Hangup".to_string(),
+        };
+        let value = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(value["code"], "hotkey_conflict");
+        assert_eq!(value["message"], "Hotkey 'Ctrl+Alt+J' is already in use: bound to Hangup");
+        assert_eq!(value["details"]["hotkey"], "Ctrl+Alt+J");
+    }
+
+    #[test]
+    fn test_settings_classification() {
+        let io = std::fs::read_to_string("/nonexistent/blink/settings.json")
+            .context("Failed to read settings")
+            .unwrap_err();
+        assert_eq!(AppError::settings("Load", io).code(), "settings_io");
+
+        let parse = serde_json::from_str::<Value>("{")
+            .context("Failed to parse")
+            .unwrap_err();
+        assert_eq!(AppError::settings("Load", parse).code(), "settings_parse");
+
+        assert_eq!(AppError::settings("Load", SettingsLocked.into()), AppError::SettingsLocked);
+        assert_eq!(AppError::settings("Unlock", anyhow::anyhow!("Wrong passphrase")).code(), "validation");
+    }
+    
+    #[test]
+    fn test_target_conflict_details() {
+        use blink::models::{CallDefaults, Target, TargetType};
+        
+        let target = |id: &str, code: &str| Target {
+            id: id.to_string(),
+            label: id.to_string(),
+            code: code.into(),
+            target_type: TargetType::Person,
+            is_primary: false,
+            call_defaults: CallDefaults::default(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            notes: None,
+            channels: Vec::new(),
+        };
+        let conflict = blink::storage::conflicts::find_conflict(&[target("a", "ab-cd")], &target("b", "abcd")).unwrap();
+        
+        let error = AppError::settings("Failed to add target", conflict.into());
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["code"], "target_conflict");
+        assert_eq!(value["details"]["kind"], "same_room");
+        assert_eq!(value["details"]["existing_id"], "a");
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_details_null_without_fields() {
+        let value = serde_json::to_value(AppError::SettingsLocked).unwrap();
+        assert_eq!(value["code"], "settings_locked");
+        assert!(value["details"].is_null());
+    }
+}
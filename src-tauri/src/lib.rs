@@ -1,30 +1,54 @@
 use tauri::{
-    menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Listener, Manager, WebviewUrl, WebviewWindowBuilder,
+    AppHandle, Emitter, Listener, Manager,
 };
 
 mod commands;
+mod error;
 mod state;
 mod services;
 mod controllers;
 
 use state::AppState;
 use services::global_shortcuts::{GlobalShortcutService, ShortcutAction};
-use services::conference_window::{ConferenceWindow, ConferenceConfig};
-use controllers::call_controller::CallController;
+use services::conference_window::ConferenceWindow;
+use services::tray_menu;
+use services::tray_icon;
+use services::window_manager::{WindowKind, WindowManager};
+use services::notifier::{self, Notification, Notifier};
+use controllers::call_controller::{join_path, CallController, JoinPath};
 use std::sync::Mutex;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .plugin(tauri_plugin_log::Builder::new().build())
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                // Default layout, but pairing codes and rooms never reach stdout/tauri.log
+                .format(|out, message, record| {
+                    out.finish(format_args!(
+                        "[{}][{}][{}] {}",
+                        tauri_plugin_log::TimezoneStrategy::UseUtc.get_now(),
+                        record.target(),
+                        record.level(),
+                        blink::core::scrub_secrets(&message.to_string())
+                    ))
+                })
+                .build(),
+        )
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
             // Initialize settings store
             let settings_store = match blink::storage::SettingsStore::load() {
                 Ok(store) => store,
+                Err(e) if e.downcast_ref::<blink::storage::SettingsLocked>().is_some() => {
+                    // Passphrase-protected: start locked, settings UI calls unlock_settings
+                    log::info!("Settings are passphrase-protected, waiting for unlock");
+                    blink::storage::SettingsStore::new_locked(
+                        blink::storage::SettingsStore::default_path()?
+                    )
+                }
                 Err(e) => {
                     log::error!("Failed to load settings: {}", e);
                     log::info!("Using default settings");
@@ -37,18 +61,16 @@ pub fn run() {
                 }
             };
             
-            // Create global shortcut service
-            let mut shortcuts_service = GlobalShortcutService::new(app.handle().clone());
-            
-            // Setup default hotkeys from settings
-            {
-                let store = &settings_store;
-                let settings = store.settings();
-                if let Err(e) = shortcuts_service.setup_default_hotkeys(&settings.keybinds) {
-                    log::error!("Failed to setup default hotkeys: {}", e);
-                }
+            for issue in settings_store.security_warnings() {
+                log::warn!("Settings security: {}", issue);
+            }
+            for warning in settings_store.data_warnings() {
+                log::warn!("Settings data: {}", warning);
             }
             
+            // Create global shortcut service
+            let shortcuts_service = GlobalShortcutService::new(app.handle().clone());
+            
             // Create conference window manager
             let conference_window = ConferenceWindow::new(app.handle().clone());
             
@@ -61,18 +83,38 @@ pub fn run() {
                 shortcuts: Mutex::new(shortcuts_service),
                 conference_window: Mutex::new(conference_window),
                 call_controller: Mutex::new(call_controller),
+                notifier: Notifier::system(),
             });
-            // Create menu items
-            let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let settings_i = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
             
-            // Create menu
-            let menu = Menu::with_items(app, &[&settings_i, &quit_i])?;
+            // Setup default hotkeys from settings (after manage: failures notify through AppState)
+            {
+                let state = app.state::<AppState>();
+                let keybinds = state.settings_store.lock().unwrap().settings().keybinds.clone();
+                let result = state.shortcuts.lock().unwrap().setup_default_hotkeys(&keybinds);
+                if let Err(e) = result {
+                    log::error!("Failed to setup default hotkeys: {}", e);
+                }
+            }
+            
+            // Hidden, loaded conference window for instant embedded joins (optional)
+            {
+                let state = app.state::<AppState>();
+                let prewarm = state.settings_store.lock().unwrap()
+                    .settings().app_settings.prewarm_conference_window;
+                if prewarm {
+                    if let Err(e) = ConferenceWindow::prewarm(app.handle()) {
+                        log::error!("Failed to pre-warm conference window: {}", e);
+                    }
+                }
+            }
+            // Create menu (Join/Recent submenus, in-call actions, Settings, Quit)
+            let tray_model = tray_menu::TrayModel::current(app.handle());
+            let menu = tray_menu::build_menu(app, &tray_model)?;
             
-            // Create tray icon with menu
-            let _tray = TrayIconBuilder::new()
-                .icon(app.default_window_icon().unwrap().clone())
-                .tooltip("Blink - Right-click for menu")
+            // Create tray icon with menu (icon follows the call state, see tray_icon)
+            let _tray = TrayIconBuilder::with_id(tray_menu::TRAY_ID)
+                .icon(tray_icon::icon(tray_model.icon_state()))
+                .tooltip(tray_menu::tooltip(&tray_model))
                 .menu(&menu)
                 .show_menu_on_left_click(false) // Right-click only for menu
                 .on_menu_event(|app, event| match event.id.as_ref() {
@@ -82,33 +124,21 @@ pub fn run() {
                     }
                     "settings" => {
                         log::info!("User selected settings from tray menu");
-                        // Show settings window or create it
-                        match app.get_webview_window("settings") {
-                            Some(window) => {
-                                // Window exists, show and focus it
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                            None => {
-                                // Create settings window
-                                let _settings_window = WebviewWindowBuilder::new(
-                                    app,
-                                    "settings",
-                                    WebviewUrl::App("settings.html".into())
-                                )
-                                .title("Blink Settings")
-                                .inner_size(700.0, 600.0)
-                                .resizable(true)
-                                .build()
-                                .expect("failed to build settings window");
-                            }
+                        // A failure here must not take the tray down
+                        if let Err(e) = WindowManager::show(app, WindowKind::Settings) {
+                            log::error!("{}", e);
                         }
                     }
-                    _ => {
-                        log::warn!("Unknown menu item: {:?}", event.id);
-                    }
+                    id => match tray_menu::menu_action(id) {
+                        // Same path as the hotkeys, handled by the "hotkey-pressed" listener
+                        Some(action) => {
+                            log::info!("User selected {:?} from tray menu", action);
+                            let _ = app.emit("hotkey-pressed", &action);
+                        }
+                        None => log::warn!("Unknown menu item: {:?}", event.id),
+                    },
                 })
-                .on_tray_icon_event(|_tray, event| {
+                .on_tray_icon_event(|tray, event| {
                     // Only log significant events, not every mouse movement
                     match event {
                         TrayIconEvent::Click {
@@ -116,7 +146,21 @@ pub fn run() {
                             button_state: MouseButtonState::Up,
                             ..
                         } => {
-                            log::debug!("Tray icon left-clicked");
+                            let app = tray.app_handle();
+                            let setting = app.state::<AppState>().settings_store.lock().unwrap()
+                                .settings().app_settings.tray_left_click;
+                            log::debug!("Tray icon left-clicked ({:?})", setting);
+                            match tray_icon::left_click_action(setting) {
+                                // Same path as the hotkeys, like the tray menu items
+                                Some(action) => {
+                                    let _ = app.emit("hotkey-pressed", &action);
+                                }
+                                None => {
+                                    if let Err(e) = WindowManager::show(app, WindowKind::Settings) {
+                                        log::error!("{}", e);
+                                    }
+                                }
+                            }
                         }
                         TrayIconEvent::DoubleClick { .. } => {
                             log::debug!("Tray icon double-clicked");
@@ -141,24 +185,16 @@ pub fn run() {
             let app_state = app.state::<AppState>();
             let is_first_run = {
                 let settings_store = app_state.settings_store.lock().unwrap();
-                settings_store.get_targets().is_empty()
+                settings_store.get_targets().is_empty() && !settings_store.is_locked()
             };
             
             if is_first_run {
                 log::info!("First run detected - showing welcome");
                 
                 // Auto-open settings window for first-time users
-                let _welcome_window = WebviewWindowBuilder::new(
-                    app,
-                    "welcome",
-                    WebviewUrl::App("settings.html?welcome=true".into())
-                )
-                .title("Welcome to Blink - Let's Get Started!")
-                .inner_size(750.0, 650.0)
-                .center()
-                .resizable(true)
-                .build()
-                .expect("failed to build welcome window");
+                if let Err(e) = WindowManager::show(app.handle(), WindowKind::Welcome) {
+                    log::error!("{}", e);
+                }
             }
             
             // Listen for hotkey events
@@ -174,75 +210,90 @@ pub fn run() {
                         ShortcutAction::JoinPrimary => {
                             log::info!("Join primary target requested");
                             
-                            // Get primary target from settings
                             let settings_store = state.settings_store.lock().unwrap();
-                            if let Some(target) = settings_store.get_primary_target() {
-                                log::info!("Primary target found: {} with code: {}", target.label, target.code);
-                                let room_id = blink::core::room_id_from_code(&target.code);
-                                log::info!("Generated room ID from code '{}': '{}'", target.code, room_id);
-                                let config = ConferenceConfig {
-                                    room_id: room_id.clone(),
-                                    display_name: "You".to_string(),
-                                    start_with_audio_muted: !target.call_defaults.start_with_audio,
-                                    start_with_video_muted: !target.call_defaults.start_with_video,
-                                    always_on_top: settings_store.settings().app_settings.always_on_top,
-                                };
-                                let target_id = target.id.clone();
-                                drop(settings_store);
-                                
-                                // Open directly in browser instead of using conference window
-                                use services::external_browser::ExternalBrowserService;
-                                if let Err(e) = ExternalBrowserService::open_meeting(&app_handle, &room_id) {
-                                    log::error!("Failed to open meeting in browser: {}", e);
-                                    // TODO: Show toast notification
+                            let request = settings_store.get_primary_target().map(|target| {
+                                log::info!("Primary target found: {} ({})", target.label, target.id);
+                                (target.id.clone(), join_path(settings_store.settings(), target, None))
+                            });
+                            drop(settings_store);
+                            
+                            match request {
+                                Some((target_id, path)) => join_call(&app_handle, target_id, None, path),
+                                None => {
+                                    log::warn!("No primary target configured");
+                                    notifier::notify(&app_handle, Notification::no_primary_target());
                                 }
-                            } else {
-                                log::warn!("No primary target configured");
                             }
                         }
                         ShortcutAction::JoinTarget { id } => {
                             log::info!("Join target {} requested", id);
                             
-                            // Get target from settings
                             let settings_store = state.settings_store.lock().unwrap();
-                            if let Some(target) = settings_store.get_target(&id) {
-                                let room_id = blink::core::room_id_from_code(&target.code);
-                                drop(settings_store);
-                                
-                                // Open directly in browser instead of using conference window
-                                use services::external_browser::ExternalBrowserService;
-                                if let Err(e) = ExternalBrowserService::open_meeting(&app_handle, &room_id) {
-                                    log::error!("Failed to open meeting in browser: {}", e);
-                                    // TODO: Show toast notification
-                                }
-                            } else {
-                                log::warn!("Target {} not found", id);
+                            let path = settings_store.get_target(&id)
+                                .map(|target| join_path(settings_store.settings(), target, None));
+                            drop(settings_store);
+                            
+                            match path {
+                                Some(path) => join_call(&app_handle, id, None, path),
+                                None => log::warn!("Target {} not found", id),
+                            }
+                        }
+                        ShortcutAction::JoinChannel { id } => {
+                            log::info!("Join channel {} requested", id);
+                            
+                            let settings_store = state.settings_store.lock().unwrap();
+                            let request = settings_store.find_channel(&id).map(|(target, channel)| {
+                                log::info!("Channel found: {} / {}", target.label, channel.name);
+                                (target.id.clone(), join_path(settings_store.settings(), target, Some(channel)))
+                            });
+                            drop(settings_store);
+                            
+                            match request {
+                                Some((target_id, path)) => join_call(&app_handle, target_id, Some(id), path),
+                                None => log::warn!("Channel {} not found", id),
                             }
                         }
                         ShortcutAction::Hangup => {
-                            log::info!("Hangup requested - not applicable when using external browser");
-                            // When using external browser, users must close the browser tab/window manually
+                            log::info!("Hangup requested");
+                            let mut window = state.conference_window.lock().unwrap();
+                            let controller = state.call_controller.lock().unwrap();
+                            if let Err(e) = controller.hangup(&mut window) {
+                                log::error!("Failed to hang up: {}", e);
+                            }
+                        }
+                        ShortcutAction::ToggleMute | ShortcutAction::ToggleVideo => {
+                            let mut window = state.conference_window.lock().unwrap();
+                            let controller = state.call_controller.lock().unwrap();
+                            let result = if action == ShortcutAction::ToggleMute {
+                                controller.toggle_mute(&mut window)
+                            } else {
+                                controller.toggle_video(&mut window)
+                            };
+                            if let Err(e) = result {
+                                log::error!("Failed to send {:?}: {}", action, e);
+                            }
+                        }
+                        ShortcutAction::ToggleMiniMode => {
+                            let mut window = state.conference_window.lock().unwrap();
+                            if let Err(e) = window.toggle_mini_mode() {
+                                log::error!("Failed to toggle mini mode: {}", e);
+                            }
                         }
                     }
                 }
             });
             
-            // Listen for conference events
-            let app_handle_clone = app.handle().clone();
-            app.listen("videoConferenceJoined", move |_| {
-                log::info!("Conference joined event received");
-                let state = app_handle_clone.state::<AppState>();
-                let controller = state.call_controller.lock().unwrap();
-                controller.on_conference_joined();
-            });
+            // Tray icon, tooltip and Hang up/Mute items follow the call and mute state
+            for event in ["call-state-changed", "mute-changed"] {
+                let app_handle = app.handle().clone();
+                app.listen(event, move |_| {
+                    // Emitted while the call controller is locked; refresh locks it too
+                    let app_handle = app_handle.clone();
+                    std::thread::spawn(move || tray_menu::refresh(&app_handle));
+                });
+            }
             
-            let app_handle_clone2 = app.handle().clone();
-            app.listen("videoConferenceLeft", move |_| {
-                log::info!("Conference left event received");
-                let state = app_handle_clone2.state::<AppState>();
-                let controller = state.call_controller.lock().unwrap();
-                controller.on_conference_left();
-            });
+            // Conference events arrive through commands::emit_to_window
             
             log::info!("Blink initialized successfully");
             Ok(())
@@ -251,10 +302,56 @@ pub fn run() {
             commands::get_settings,
             commands::save_settings,
             commands::generate_code,
+            commands::check_code_strength,
+            commands::parse_meeting_link,
             commands::validate_hotkey,
             commands::test_hotkey,
             commands::remove_target,
+            commands::get_target,
+            commands::add_target,
+            commands::update_target,
+            commands::set_primary_target,
+            commands::reorder_targets,
+            commands::get_target_conflicts,
+            commands::merge_targets,
+            commands::add_channel,
+            commands::remove_channel,
+            commands::export_settings,
+            commands::preview_import,
+            commands::import_settings,
+            commands::get_encryption_status,
+            commands::unlock_settings,
+            commands::set_code_encryption,
+            commands::emit_to_window,
+            commands::get_participants,
+            commands::postpone_auto_hangup,
+            commands::send_conference_command,
+            commands::open_window,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}
+
+/// Join through the CallController (conference window or browser, per join mode)
+/// and add the call to the tray's Recent list
+/// Lock order: conference_window, then call_controller
+fn join_call(app_handle: &AppHandle, target_id: String, channel_id: Option<String>, path: JoinPath) {
+    let state = app_handle.state::<AppState>();
+    let joined = {
+        let mut window = state.conference_window.lock().unwrap();
+        let controller = state.call_controller.lock().unwrap();
+        controller.join(target_id.clone(), path, &mut window)
+    };
+    if let Err(e) = joined {
+        log::error!("Failed to join call: {}", e);
+        notifier::notify(app_handle, Notification::join_failed(&e));
+        return;
+    }
+    
+    let recorded = state.settings_store.lock().unwrap()
+        .record_recent_call(&target_id, channel_id.as_deref());
+    if let Err(e) = recorded {
+        log::error!("Failed to record recent call: {}", e);
+    }
+    tray_menu::refresh(app_handle);
+}
@@ -0,0 +1,281 @@
+// Conference events
+// What: Typed, validated events forwarded from conference.html (Jitsi iframe API)
+// Why: Page payloads are untrusted JSON; CallController should only ever see
+//      known events with checked fields
+// Used by: commands::emit_to_window, CallController::handle_event() (Roster)
+// Change notes: Event names are Jitsi's; conference.html forwards them unchanged
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Longest participant id / room name accepted from the page
+const MAX_ID_LEN: usize = 256;
+/// Display names longer than this are cut (Jitsi itself allows very long ones)
+const MAX_DISPLAY_NAME_CHARS: usize = 100;
+
+/// A participant as reported by Jitsi
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct Participant {
+    pub id: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+/// Event from the conference page
+/// What: One variant per Jitsi event we react to, plus the page's dom-ready
+/// Contract: Serializes as {"type": "participantJoined", ...fields}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConferenceEvent {
+    /// Page listeners are set up; ConferenceWindow shows the window
+    DomReady,
+    /// We're in the meeting
+    VideoConferenceJoined {
+        room_name: Option<String>,
+        /// Our own participant id
+        id: Option<String>,
+    },
+    /// We left the meeting (hangup button, kicked, connection lost)
+    VideoConferenceLeft { room_name: Option<String> },
+    ParticipantJoined(Participant),
+    ParticipantLeft { id: String },
+    /// Our microphone was muted/unmuted (button, hotkey or moderator)
+    AudioMuteStatusChanged { muted: bool },
+    /// Jitsi is done with the meeting and the window may close
+    ReadyToClose,
+}
+
+/// Other participants in the current call, in join order
+/// What: Kept up to date from ParticipantJoined/Left
+/// Why: Show who's there; auto-hangup when nobody is
+/// Used by: CallController, get_participants command, "participants-changed" event
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Roster {
+    participants: Vec<Participant>,
+}
+
+impl Roster {
+    /// Apply a participant event; returns true if the roster changed
+    /// Contract: A repeated ParticipantJoined updates the display name in place
+    pub fn apply(&mut self, event: &ConferenceEvent) -> bool {
+        match event {
+            ConferenceEvent::ParticipantJoined(joined) => {
+                match self.participants.iter_mut().find(|p| p.id == joined.id) {
+                    Some(existing) if existing == joined => false,
+                    Some(existing) => {
+                        *existing = joined.clone();
+                        true
+                    }
+                    None => {
+                        self.participants.push(joined.clone());
+                        true
+                    }
+                }
+            }
+            ConferenceEvent::ParticipantLeft { id } => {
+                let before = self.participants.len();
+                self.participants.retain(|p| p.id != *id);
+                self.participants.len() != before
+            }
+            _ => false,
+        }
+    }
+
+    pub fn participants(&self) -> &[Participant] {
+        &self.participants
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.participants.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.participants.clear();
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoomPayload {
+    #[serde(default)]
+    room_name: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdPayload {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct MutedPayload {
+    muted: bool,
+}
+
+impl ConferenceEvent {
+    /// parse(event, payload)
+    /// What: Builds a validated event from the page's event name and JSON payload
+    /// Contract:
+    ///   - Unknown event names and malformed payloads are Validation errors
+    ///   - Ids/room names must be non-empty, at most MAX_ID_LEN, no control chars
+    ///   - Display names are trimmed, stripped of control chars and cut to
+    ///     MAX_DISPLAY_NAME_CHARS; blank ones become None
+    pub fn parse(event: &str, payload: Option<Value>) -> Result<Self, AppError> {
+        let payload = payload.unwrap_or(Value::Null);
+        let parsed = match event {
+            "dom-ready" => ConferenceEvent::DomReady,
+            "readyToClose" => ConferenceEvent::ReadyToClose,
+            "videoConferenceJoined" => {
+                let room: RoomPayload = from_payload(event, payload)?;
+                ConferenceEvent::VideoConferenceJoined {
+                    room_name: room.room_name.map(|r| check_id(event, "roomName", r)).transpose()?,
+                    id: room.id.map(|id| check_id(event, "id", id)).transpose()?,
+                }
+            }
+            "videoConferenceLeft" => {
+                let room: RoomPayload = from_payload(event, payload)?;
+                ConferenceEvent::VideoConferenceLeft {
+                    room_name: room.room_name.map(|r| check_id(event, "roomName", r)).transpose()?,
+                }
+            }
+            "participantJoined" => {
+                let participant: Participant = from_payload(event, payload)?;
+                ConferenceEvent::ParticipantJoined(Participant {
+                    id: check_id(event, "id", participant.id)?,
+                    display_name: participant.display_name.and_then(clean_display_name),
+                })
+            }
+            "participantLeft" => {
+                let left: IdPayload = from_payload(event, payload)?;
+                ConferenceEvent::ParticipantLeft { id: check_id(event, "id", left.id)? }
+            }
+            "audioMuteStatusChanged" => {
+                let status: MutedPayload = from_payload(event, payload)?;
+                ConferenceEvent::AudioMuteStatusChanged { muted: status.muted }
+            }
+            other => {
+                return Err(AppError::Validation { message: format!("Unknown conference event '{}'", other) });
+            }
+        };
+        Ok(parsed)
+    }
+}
+
+fn from_payload<T: for<'de> Deserialize<'de>>(event: &str, payload: Value) -> Result<T, AppError> {
+    // Unit-like payloads ({} or nothing) are fine for events whose fields are optional
+    let payload = if payload.is_null() { Value::Object(Default::default()) } else { payload };
+    serde_json::from_value(payload)
+        .map_err(|e| AppError::Validation { message: format!("Invalid {} payload: {}", event, e) })
+}
+
+fn check_id(event: &str, field: &str, value: String) -> Result<String, AppError> {
+    if value.is_empty() || value.len() > MAX_ID_LEN || value.chars().any(char::is_control) {
+        return Err(AppError::Validation { message: format!("Invalid {} in {} payload", field, event) });
+    }
+    Ok(value)
+}
+
+fn clean_display_name(name: String) -> Option<String> {
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    let name: String = name.trim().chars().take(MAX_DISPLAY_NAME_CHARS).collect();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_jitsi_events() {
+        let joined = ConferenceEvent::parse(
+            "videoConferenceJoined",
+            Some(json!({ "roomName": "justcallroomabcd2345", "id": "a1b2c3d4", "displayName": "You" })),
+        ).unwrap();
+        assert_eq!(joined, ConferenceEvent::VideoConferenceJoined {
+            room_name: Some("justcallroomabcd2345".into()),
+            id: Some("a1b2c3d4".into()),
+        });
+
+        let participant = ConferenceEvent::parse(
+            "participantJoined",
+            Some(json!({ "id": "e5f6", "displayName": "  Alice\u{0007} " })),
+        ).unwrap();
+        assert_eq!(participant, ConferenceEvent::ParticipantJoined(Participant {
+            id: "e5f6".into(),
+            display_name: Some("Alice".into()),
+        }));
+
+        assert_eq!(
+            ConferenceEvent::parse("participantLeft", Some(json!({ "id": "e5f6" }))).unwrap(),
+            ConferenceEvent::ParticipantLeft { id: "e5f6".into() }
+        );
+        assert_eq!(
+            ConferenceEvent::parse("audioMuteStatusChanged", Some(json!({ "muted": true }))).unwrap(),
+            ConferenceEvent::AudioMuteStatusChanged { muted: true }
+        );
+        assert_eq!(ConferenceEvent::parse("readyToClose", Some(json!({}))).unwrap(), ConferenceEvent::ReadyToClose);
+        assert_eq!(ConferenceEvent::parse("dom-ready", None).unwrap(), ConferenceEvent::DomReady);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let event = ConferenceEvent::ParticipantJoined(Participant { id: "e5f6".into(), display_name: None });
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json, json!({ "type": "participantJoined", "id": "e5f6", "display_name": null }));
+    }
+
+    #[test]
+    fn test_roster() {
+        let alice = Participant { id: "a".into(), display_name: Some("Alice".into()) };
+        let bob = Participant { id: "b".into(), display_name: None };
+        let mut roster = Roster::default();
+
+        assert!(roster.apply(&ConferenceEvent::ParticipantJoined(alice.clone())));
+        assert!(roster.apply(&ConferenceEvent::ParticipantJoined(bob.clone())));
+        assert!(!roster.apply(&ConferenceEvent::ParticipantJoined(alice.clone())));
+        assert_eq!(roster.participants(), [alice.clone(), bob.clone()]);
+
+        // Rename keeps the position
+        let renamed = Participant { id: "a".into(), display_name: Some("Al".into()) };
+        assert!(roster.apply(&ConferenceEvent::ParticipantJoined(renamed.clone())));
+        assert_eq!(roster.participants()[0], renamed);
+
+        assert!(roster.apply(&ConferenceEvent::ParticipantLeft { id: "a".into() }));
+        assert!(roster.apply(&ConferenceEvent::ParticipantLeft { id: "b".into() }));
+        assert!(roster.is_empty());
+        assert_eq!(serde_json::to_value(&roster).unwrap(), json!([]));
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_roster_ignores_unknown_and_other_events() {
+        let mut roster = Roster::default();
+        assert!(!roster.apply(&ConferenceEvent::ParticipantLeft { id: "ghost".into() }));
+        assert!(!roster.apply(&ConferenceEvent::ReadyToClose));
+    }
+
+    #[test]
+    fn test_rejects_bad_events() {
+        let error = ConferenceEvent::parse("startRecording", None).unwrap_err();
+        assert_eq!(error.code(), "validation");
+
+        assert!(ConferenceEvent::parse("participantJoined", Some(json!({}))).is_err());
+        assert!(ConferenceEvent::parse("participantJoined", Some(json!({ "id": 42 }))).is_err());
+        assert!(ConferenceEvent::parse("participantLeft", Some(json!({ "id": "" }))).is_err());
+        assert!(ConferenceEvent::parse("participantLeft", Some(json!({ "id": "x".repeat(MAX_ID_LEN + 1) }))).is_err());
+        assert!(ConferenceEvent::parse("videoConferenceLeft", Some(json!("room"))).is_err());
+        assert!(ConferenceEvent::parse("audioMuteStatusChanged", None).is_err());
+    }
+
+    #[test]
+    fn test_display_name_limits() {
+        assert_eq!(clean_display_name("   ".into()), None);
+        assert_eq!(clean_display_name("é".repeat(150)).unwrap().chars().count(), MAX_DISPLAY_NAME_CHARS);
+    }
+}
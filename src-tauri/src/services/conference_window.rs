@@ -3,10 +3,30 @@
 /// Why: Provides clean interface for window creation and management
 /// Used by: CallController (phase 5), hotkey handlers
 /// Calls: Tauri window API, emits window events
-/// Change notes: Enforces single window instance, handles edge cases
+/// Change notes: Enforces single window instance, handles edge cases;
+///               geometry is remembered per target (CallDefaults.window);
+///               a destroyed window (title-bar X included) ends the call
 
-use tauri::{WebviewUrl, WebviewWindowBuilder, Emitter, Listener, Manager};
-use serde::Serialize;
+use crate::error::AppError;
+use crate::state::AppState;
+use blink::core::{mini_window, place_window, MonitorArea, Rect};
+use blink::models::WindowGeometry;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{LogicalSize, Monitor, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder, Emitter, Listener, Manager, WindowEvent};
+use serde::{Deserialize, Serialize};
+
+/// Label of the (single) conference window
+pub const WINDOW_LABEL: &str = "conference";
+
+/// Event conference.html listens on for ConferenceCommands
+const COMMAND_EVENT: &str = "conference-command";
+
+/// Time Jitsi gets to leave the meeting before the window closes
+const CLOSE_DELAY: Duration = Duration::from_millis(100);
+
+/// Longest display name / subject accepted by SetDisplayName / SetSubject
+const MAX_TEXT_CHARS: usize = 100;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ConferenceConfig {
@@ -15,6 +35,78 @@ pub struct ConferenceConfig {
     pub start_with_audio_muted: bool,
     pub start_with_video_muted: bool,
     pub always_on_top: bool,
+    /// Target whose window geometry is restored and saved (not sent to the page)
+    #[serde(skip)]
+    pub target_id: String,
+    /// Geometry to restore (target's CallDefaults.window)
+    #[serde(skip)]
+    pub geometry: Option<WindowGeometry>,
+}
+
+/// Command for the Jitsi call in the conference window
+/// What: One variant per Jitsi external API command we use; the serde names
+///       are Jitsi's executeCommand() names
+/// Contract: Serializes as {"command": "toggleAudio"} or
+///           {"command": "displayName", "arg": "Alice"}
+/// Change notes: conference.html only runs commands it knows; add new
+///               variants there too
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", content = "arg")]
+pub enum ConferenceCommand {
+    #[serde(rename = "toggleAudio")]
+    ToggleMute,
+    #[serde(rename = "toggleVideo")]
+    ToggleVideo,
+    #[serde(rename = "toggleShareScreen")]
+    ToggleScreenShare,
+    #[serde(rename = "toggleRaiseHand")]
+    RaiseHand,
+    #[serde(rename = "displayName")]
+    SetDisplayName(String),
+    #[serde(rename = "subject")]
+    SetSubject(String),
+    #[serde(rename = "hangup")]
+    Hangup,
+}
+
+impl ConferenceCommand {
+    /// Checks text arguments (display name, subject) before they reach Jitsi
+    /// Contract: Non-blank, at most MAX_TEXT_CHARS, no control characters
+    pub fn validate(&self) -> Result<(), AppError> {
+        let (field, text) = match self {
+            ConferenceCommand::SetDisplayName(name) => ("Display name", name),
+            ConferenceCommand::SetSubject(subject) => ("Subject", subject),
+            _ => return Ok(()),
+        };
+        if text.trim().is_empty() || text.chars().count() > MAX_TEXT_CHARS || text.chars().any(char::is_control) {
+            return Err(AppError::Validation {
+                message: format!("{} must be 1-{} characters without control characters", field, MAX_TEXT_CHARS),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Window size state shared with the window's close handler
+#[derive(Debug, Default)]
+struct Placement {
+    /// Target the window geometry is saved for
+    target_id: String,
+    /// Regular geometry while the window is in mini mode
+    normal: Option<WindowGeometry>,
+    /// Window always-on-top setting outside mini mode
+    always_on_top: bool,
+}
+
+/// Page state of the current conference window
+#[derive(Debug, Default)]
+struct Warmup {
+    /// The page sent dom-ready (its listeners are set up)
+    ready: bool,
+    /// A call was started in this window (it's no longer a spare)
+    in_use: bool,
+    /// Call waiting for dom-ready
+    pending: Option<ConferenceConfig>,
 }
 
 pub struct ConferenceWindow {
@@ -22,6 +114,20 @@ pub struct ConferenceWindow {
     window: Option<tauri::WebviewWindow>,
     /// App handle for creating windows
     app_handle: tauri::AppHandle,
+    /// Geometry bookkeeping for the active window
+    placement: Arc<Mutex<Placement>>,
+    /// Page readiness, shared with the dom-ready listener
+    warmup: Arc<Mutex<Warmup>>,
+    /// Bumped for every window built; a Destroyed event only clears the
+    /// handle (and ends the call) if it belongs to the current window
+    generation: u64,
+    /// prewarm() is building the window (without holding this manager's lock)
+    warming: bool,
+    /// Generation of the window close() is closing; its label is taken until
+    /// the Destroyed event
+    closing: Option<u64>,
+    /// Call started while a window was being built or closed
+    queued: Option<ConferenceConfig>,
 }
 
 impl ConferenceWindow {
@@ -33,136 +139,299 @@ impl ConferenceWindow {
         Self {
             window: None,
             app_handle,
+            placement: Arc::new(Mutex::new(Placement::default())),
+            warmup: Arc::new(Mutex::new(Warmup::default())),
+            generation: 0,
+            warming: false,
+            closing: None,
+            queued: None,
         }
     }
     
     /// Open conference window
-    /// What: Creates or shows the video call window
+    /// What: Starts a call in the conference window, creating it if needed
     /// Why: Entry point for starting a video call
     /// Contract:
     /// - config: Room and display settings
-    /// - Reuses existing window if open
-    /// - Window is centered, 1024x768 default
+    /// - Reuses the existing (e.g. pre-warmed) window if there is one; the
+    ///   call starts as soon as its page is ready
+    /// - Window is centered, 1024x768 default, unless the target has a
+    ///   remembered geometry (fitted to the monitors connected now)
     /// - Returns error if window creation fails
     /// Used by: CallController::join() (phase 5)
     /// Calls: Tauri WebviewWindowBuilder
-    /// Events: Emits "conference-window-ready" after creation
+    /// Events: Emits "start-call" to the page, "conference-window-ready" once shown
     /// Change notes: If changing window size, update conference.html responsive CSS
-    pub fn open(&mut self, config: ConferenceConfig) -> Result<(), String> {
+    pub fn open(&mut self, config: ConferenceConfig) -> Result<(), AppError> {
         log::info!("Opening conference window for room: {}", config.room_id);
         
-        // Check if window already exists in Tauri's window manager
-        let window_label = "conference";
-        if let Some(existing) = self.app_handle.get_webview_window(window_label) {
-            log::info!("Conference window already exists, focusing");
-            let _ = existing.show();
-            let _ = existing.set_focus();
-            
-            // Update room config for existing window
-            existing.emit("start-call", &config)
-                .map_err(|e| format!("Failed to emit to existing window: {}", e))?;
-            
-            // Update our reference
-            self.window = Some(existing);
-            return Ok(());
+        // Only our live handle is reused - a window being closed keeps its
+        // label until it's destroyed
+        let window = match self.window.clone() {
+            Some(existing) => {
+                log::info!("Conference window already exists, reusing it");
+                existing
+            }
+            None if self.warming || self.closing.is_some() => {
+                // prewarm() starts the call once its window is built (after
+                // the closing one is destroyed, see handle_destroyed())
+                log::info!("Conference window still being pre-warmed or closed, queueing the call");
+                self.queued = Some(config);
+                return Ok(());
+            }
+            None => {
+                let window = build_webview(&self.app_handle)?;
+                self.attach(&window);
+                window
+            }
+        };
+        self.start(window, config);
+        Ok(())
+    }
+    
+    /// Show the call in `window` (now or once its page is ready)
+    fn start(&mut self, window: tauri::WebviewWindow, config: ConferenceConfig) {
+        // Still hidden if new or pre-warmed - move it before it's shown
+        if let Some(saved) = &config.geometry {
+            restore_geometry(&window, saved);
+        }
+        if let Err(e) = window.set_always_on_top(config.always_on_top) {
+            log::warn!("Failed to set always-on-top: {}", e);
         }
+        *self.placement.lock().unwrap() = Placement {
+            target_id: config.target_id.clone(),
+            normal: None,
+            always_on_top: config.always_on_top,
+        };
         
-        // Create new window
-        let window_label = "conference";
+        let mut warmup = self.warmup.lock().unwrap();
+        warmup.in_use = true;
+        if warmup.ready {
+            start_call(&self.app_handle, &window, &config);
+        } else {
+            // The dom-ready listener starts it
+            warmup.pending = Some(config);
+        }
+        drop(warmup);
         
-        // Encode config as URL parameter
-        let config_json = serde_json::to_string(&config).unwrap_or_default();
-        let encoded_config = urlencoding::encode(&config_json);
-        let url = format!("conference.html?config={}", encoded_config);
+        self.window = Some(window);
+    }
+    
+    /// Pre-warm the conference window
+    /// What: Creates the hidden conference window (page and Jitsi script
+    ///       loaded, no meeting) so a join only has to emit "start-call"
+    /// Why: Building a webview and waiting for dom-ready is most of the join time
+    /// Used by: App setup and after each call (AppSettings.prewarm_conference_window),
+    ///          save_settings when the setting is turned on
+    /// Contract:
+    ///   - Must be called without holding AppState.conference_window: the
+    ///     window is built unlocked and stored afterwards. Building waits for
+    ///     the main thread, whose tray and hotkey handlers take that lock
+    ///   - No-op if a conference window exists, is being built or is closing
+    ///   - A queued join (see open()) is started once the window is stored;
+    ///     if the build fails, that call ends
+    ///   - A window finished after pre-warming was turned off is closed
+    pub fn prewarm(app_handle: &tauri::AppHandle) -> Result<(), AppError> {
+        let state = app_handle.state::<AppState>();
+        {
+            let mut manager = state.conference_window.lock().unwrap();
+            if manager.warming || manager.closing.is_some() || manager.window.is_some() {
+                return Ok(());
+            }
+            manager.warming = true;
+        }
+        log::info!("Pre-warming conference window");
+        let built = build_webview(app_handle);
+        let wanted = state.settings_store.lock().unwrap()
+            .settings().app_settings.prewarm_conference_window;
         
-        let window = WebviewWindowBuilder::new(
-            &self.app_handle,
-            window_label,
-            WebviewUrl::App(url.into())
-        )
-        .title("JustCall")
-        .inner_size(1024.0, 768.0)
-        .min_inner_size(640.0, 480.0)
-        .resizable(true)
-        .always_on_top(config.always_on_top)
-        .fullscreen(false)
-        .skip_taskbar(false)  // Show in taskbar/dock
-        .decorations(true)    // Native window chrome
-        .visible(false)       // Start hidden, show after load
-        .initialization_script(
-            r#"
-            console.log('Conference window initialization script running...');
-            
-            // Override user agent to make Jitsi think we're Chrome
-            Object.defineProperty(navigator, 'userAgent', {
-                get: function() { return 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36'; }
-            });
-            
-            // Ensure Tauri API is available
-            if (!window.__TAURI__ && window.__TAURI_INTERNALS__) {
-                window.__TAURI__ = window.__TAURI_INTERNALS__;
+        // Lock order: conference_window, then call_controller
+        let mut manager = state.conference_window.lock().unwrap();
+        manager.warming = false;
+        let queued = manager.queued.take();
+        let window = match built {
+            Ok(window) => window,
+            Err(e) => {
+                if queued.is_some() {
+                    state.call_controller.lock().unwrap().on_window_closed();
+                }
+                return Err(e);
             }
-            console.log('Init script - window.__TAURI__:', window.__TAURI__);
-            "#
-        )
-        .build()
-        .map_err(|e| format!("Failed to create window: {}", e))?;
+        };
+        manager.attach(&window);
+        match queued {
+            Some(config) => manager.start(window, config),
+            None if !wanted => {
+                log::info!("Pre-warming turned off meanwhile, closing the new window");
+                manager.window = Some(window);
+                manager.close();
+            }
+            None => manager.window = Some(window),
+        }
+        Ok(())
+    }
+    
+    /// Whether the next open() finds a loaded, unused window
+    /// Used by: CallController (join latency logging)
+    pub fn is_warm(&self) -> bool {
+        let warmup = self.warmup.lock().unwrap();
+        warmup.ready && !warmup.in_use && self.window.is_some()
+    }
+    
+    /// Close a pre-warmed window that no call is using
+    /// Used by: save_settings when pre-warming is turned off
+    pub fn discard_prewarmed(&mut self) {
+        if self.window.is_some() && !self.warmup.lock().unwrap().in_use {
+            log::info!("Closing pre-warmed conference window");
+            self.close();
+        }
+    }
+    
+    /// Take over a freshly built window: close, destroy and dom-ready handlers
+    fn attach(&mut self, window: &tauri::WebviewWindow) {
+        // A new page: not ready, not in use
+        *self.warmup.lock().unwrap() = Warmup::default();
+        self.generation += 1;
+        self.save_geometry_on_close(window);
+        self.handle_destroyed(window, self.generation);
         
-        // Clone for event handlers
+        let warmup = self.warmup.clone();
         let window_clone = window.clone();
-        let config_clone = config.clone();
         let app_handle = self.app_handle.clone();
-        
-        // Clone for different approach
-        let window_clone2 = window.clone();
-        let config_json = serde_json::to_string(&config).unwrap_or_default();
-        
-        // Wait for DOM ready before showing and emitting config
-        window.once("dom-ready", move |event| {
-            log::info!("Conference window DOM ready event received: {:?}", event);
-            log::info!("Emitting start-call with config: {:?}", &config_clone);
-            
-            // Try the original emit approach
-            match window_clone.emit("start-call", &config_clone) {
-                Ok(_) => log::info!("Successfully emitted start-call event"),
-                Err(e) => {
-                    log::error!("Failed to emit start-call: {}", e);
-                    
-                    // Fallback: Try using eval to inject the config directly
-                    let js_code = format!(
-                        r#"
-                        console.log('Injecting config via eval');
-                        if (window.bridge && window.bridge.createMeeting) {{
-                            const config = {};
-                            console.log('Creating meeting with injected config:', config);
-                            window.bridge.createMeeting(config);
-                        }} else {{
-                            console.error('Bridge not ready, storing config for later');
-                            window.__injectedConfig = {};
-                        }}
-                        "#,
-                        config_json, config_json
-                    );
-                    
-                    if let Err(e) = window_clone2.eval(&js_code) {
-                        log::error!("Failed to inject config via eval: {}", e);
+        window.once("dom-ready", move |_| {
+            log::info!("Conference window DOM ready");
+            let mut warmup = warmup.lock().unwrap();
+            warmup.ready = true;
+            if let Some(config) = warmup.pending.take() {
+                start_call(&app_handle, &window_clone, &config);
+            }
+        });
+    }
+    
+    /// React to the window being destroyed (hangup, title-bar X, OS)
+    /// What: Clears the stale handle, ends the call in CallController, then
+    ///       builds the next window for a queued join or pre-warming
+    /// Why: A window closed by the user must not leave the app "in a call";
+    ///      the label is only free for a new window after Destroyed
+    /// Contract: A queued join belongs to a newer call, which is left running
+    fn handle_destroyed(&self, window: &tauri::WebviewWindow, generation: u64) {
+        let app_handle = self.app_handle.clone();
+        window.on_window_event(move |event| {
+            if !matches!(event, WindowEvent::Destroyed) {
+                return;
+            }
+            // Off the main thread: the hotkey thread may hold the window lock
+            // while waiting for the main thread
+            let app_handle = app_handle.clone();
+            std::thread::spawn(move || {
+                let state = app_handle.state::<AppState>();
+                let queued = {
+                    // Lock order: conference_window, then call_controller
+                    let mut window = state.conference_window.lock().unwrap();
+                    if window.closing == Some(generation) {
+                        window.closing = None;
+                    }
+                    let queued = window.queued.is_some();
+                    if window.forget(generation) && !queued {
+                        state.call_controller.lock().unwrap().on_window_closed();
                     }
+                    queued
+                };
+                
+                let prewarm = state.settings_store.lock().unwrap()
+                    .settings().app_settings.prewarm_conference_window;
+                if !prewarm && !queued {
+                    return;
                 }
+                if let Err(e) = ConferenceWindow::prewarm(&app_handle) {
+                    log::error!("Failed to pre-warm conference window: {}", e);
+                }
+            });
+        });
+    }
+    
+    /// Drop the handle of a destroyed window; false if it was an older window
+    fn forget(&mut self, generation: u64) -> bool {
+        if generation != self.generation {
+            return false;
+        }
+        log::info!("Conference window destroyed");
+        self.window = None;
+        true
+    }
+    
+    /// Remember the window's geometry for its target when it closes
+    /// What: CloseRequested (user or close()) stores the regular - not mini -
+    ///       geometry through SettingsStore::set_window_geometry
+    fn save_geometry_on_close(&self, window: &tauri::WebviewWindow) {
+        let placement = self.placement.clone();
+        let app_handle = self.app_handle.clone();
+        let window_clone = window.clone();
+        window.on_window_event(move |event| {
+            if !matches!(event, WindowEvent::CloseRequested { .. }) {
+                return;
+            }
+            let placement = placement.lock().unwrap();
+            if placement.target_id.is_empty() {
+                // Pre-warmed window that never had a call
+                return;
+            }
+            let geometry = match &placement.normal {
+                Some(normal) => Some(normal.clone()),
+                None => capture_geometry(&window_clone),
+            };
+            let Some(geometry) = geometry else {
+                return;
+            };
+            let state = app_handle.state::<AppState>();
+            let mut store = state.settings_store.lock().unwrap();
+            if let Err(e) = store.set_window_geometry(&placement.target_id, geometry) {
+                log::error!("Failed to save conference window geometry: {}", e);
             }
-            
-            // Show window after config sent
-            let _ = window_clone.show();
-            let _ = window_clone.set_focus();
-            
-            // Notify app that window is ready
-            app_handle.emit("conference-window-ready", ())
-                .unwrap_or_else(|e| log::error!("Failed to emit window ready: {}", e));
         });
+    }
+    
+    /// Toggle the compact mini (picture-in-picture) mode
+    /// What: Shrinks the window to an always-on-top thumbnail in the corner of
+    ///       its monitor, or restores the regular size and position
+    /// Used by: ToggleMiniMode hotkey
+    /// Contract:
+    ///   - Restoring goes through place_window(), so an unplugged monitor
+    ///     brings the window back on the primary one
+    ///   - Error without a window or monitor
+    pub fn toggle_mini_mode(&mut self) -> Result<(), AppError> {
+        let window = self.window.as_ref()
+            .ok_or_else(|| window_error("No active conference window".to_string()))?;
+        // Not held across window calls: those wait for the main thread, which
+        // may be in the close handler waiting for this lock
+        let (normal, always_on_top) = {
+            let mut placement = self.placement.lock().unwrap();
+            (placement.normal.take(), placement.always_on_top)
+        };
+        let to_window_error = |e: tauri::Error| window_error(format!("Failed to resize window: {}", e));
         
-        // Store window reference
-        self.window = Some(window);
-        
-        log::info!("Conference window created successfully");
+        match normal {
+            None => {
+                let normal = capture_geometry(window)
+                    .ok_or_else(|| window_error("Window position unavailable".to_string()))?;
+                let monitor = window.current_monitor().ok().flatten()
+                    .or_else(|| window.primary_monitor().ok().flatten())
+                    .ok_or_else(|| window_error("No monitor found".to_string()))?;
+                let mini = mini_window(&monitor_area(&monitor));
+                
+                window.set_min_size(None::<LogicalSize<f64>>).map_err(to_window_error)?;
+                window.set_always_on_top(true).map_err(to_window_error)?;
+                set_rect(window, mini).map_err(to_window_error)?;
+                log::info!("Conference window in mini mode");
+                self.placement.lock().unwrap().normal = Some(normal);
+            }
+            Some(normal) => {
+                window.set_min_size(Some(LogicalSize::new(640.0, 480.0))).map_err(to_window_error)?;
+                window.set_always_on_top(always_on_top).map_err(to_window_error)?;
+                restore_geometry(window, &normal);
+                log::info!("Conference window back to regular size");
+            }
+        }
         Ok(())
     }
     
@@ -171,24 +440,32 @@ impl ConferenceWindow {
     /// Why: Called when ending a call
     /// Used by: CallController::hangup() (phase 5)
     /// Calls: Window close API
+    /// Contract: Returns right away; the window closes shortly after on a
+    ///           background thread (callers hold the AppState locks)
     /// Events: Window emits "closed" event automatically
     pub fn close(&mut self) {
         log::info!("Closing conference window");
+        // A queued call (see open()) is dropped too
+        self.queued = None;
         
         if let Some(window) = self.window.take() {
-            // Emit cleanup event first
-            let _ = window.emit("end-call", ());
-            
-            // Small delay to allow cleanup
-            std::thread::sleep(std::time::Duration::from_millis(100));
+            // open() queues until this window is destroyed
+            self.closing = Some(self.generation);
+            // Let Jitsi hang up first
+            let _ = window.emit(COMMAND_EVENT, ConferenceCommand::Hangup);
             
-            // Close window
-            if let Err(e) = window.close() {
-                log::error!("Failed to close window: {}", e);
-            }
+            std::thread::spawn(move || {
+                // Small delay to allow cleanup
+                std::thread::sleep(CLOSE_DELAY);
+                if let Err(e) = window.close() {
+                    // Destroyed must still come, or open() keeps queueing
+                    log::error!("Failed to close window, destroying it: {}", e);
+                    if let Err(e) = window.destroy() {
+                        log::error!("Failed to destroy window: {}", e);
+                    }
+                }
+            });
         }
-        
-        self.window = None;
     }
     
     /// Check if conference window is open
@@ -204,16 +481,18 @@ impl ConferenceWindow {
     }
     
     /// Send command to conference window
-    /// What: Sends commands to the webview (mute, toggle video, etc)
+    /// What: Runs a Jitsi command (mute, toggle video, etc) in the webview
     /// Why: Allows backend to control call features
-    /// Used by: Future in-call hotkeys, tray menu actions
-    /// Events: Emits custom events to webview
-    pub fn send_command(&self, command: &str, payload: serde_json::Value) -> Result<(), String> {
+    /// Used by: CallController (in-call hotkeys, send_conference_command)
+    /// Contract: Invalid text arguments are Validation errors
+    /// Events: Emits "conference-command" to the conference window
+    pub fn send_command(&self, command: &ConferenceCommand) -> Result<(), AppError> {
+        command.validate()?;
         if let Some(window) = &self.window {
-            window.emit(command, payload)
-                .map_err(|e| format!("Failed to send command: {}", e))
+            window.emit(COMMAND_EVENT, command)
+                .map_err(|e| window_error(format!("Failed to send command: {}", e)))
         } else {
-            Err("No active conference window".to_string())
+            Err(window_error("No active conference window".to_string()))
         }
     }
     
@@ -226,6 +505,139 @@ impl ConferenceWindow {
     }
 }
 
+/// Create the (hidden) conference window
+/// Contract: Waits for the main thread; call it without holding
+///           AppState.conference_window, or from the main thread
+fn build_webview(app_handle: &tauri::AppHandle) -> Result<tauri::WebviewWindow, AppError> {
+    let window = WebviewWindowBuilder::new(
+        app_handle,
+        WINDOW_LABEL,
+        WebviewUrl::App("conference.html".into())
+    )
+    .title("JustCall")
+    .inner_size(1024.0, 768.0)
+    .min_inner_size(640.0, 480.0)
+    .resizable(true)
+    .fullscreen(false)
+    .skip_taskbar(false)  // Show in taskbar/dock
+    .decorations(true)    // Native window chrome
+    .visible(false)       // Start hidden, show once the call starts
+    .initialization_script(
+        r#"
+        console.log('Conference window initialization script running...');
+        
+        // Override user agent to make Jitsi think we're Chrome
+        Object.defineProperty(navigator, 'userAgent', {
+            get: function() { return 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36'; }
+        });
+        
+        // Ensure Tauri API is available
+        if (!window.__TAURI__ && window.__TAURI_INTERNALS__) {
+            window.__TAURI__ = window.__TAURI_INTERNALS__;
+        }
+        console.log('Init script - window.__TAURI__:', window.__TAURI__);
+        "#
+    )
+    .build()
+    .map_err(|e| window_error(format!("Failed to create window: {}", e)))?;
+    log::info!("Conference window created successfully");
+    Ok(window)
+}
+
+/// Send the call config to a ready page and show the window
+fn start_call(app_handle: &tauri::AppHandle, window: &tauri::WebviewWindow, config: &ConferenceConfig) {
+    log::info!("Emitting start-call for room: {}", config.room_id);
+    if let Err(e) = window.emit("start-call", config) {
+        log::error!("Failed to emit start-call: {}", e);
+        
+        // Fallback: inject the config directly
+        let config_json = serde_json::to_string(config).unwrap_or_default();
+        let js_code = format!(
+            r#"
+            console.log('Injecting config via eval');
+            if (window.bridge && window.bridge.createMeeting) {{
+                window.bridge.createMeeting({});
+            }} else {{
+                console.error('Bridge not ready, storing config for later');
+                window.__injectedConfig = {};
+            }}
+            "#,
+            config_json, config_json
+        );
+        if let Err(e) = window.eval(&js_code) {
+            log::error!("Failed to inject config via eval: {}", e);
+        }
+    }
+    
+    let _ = window.show();
+    let _ = window.set_focus();
+    
+    // Notify app that window is ready
+    app_handle.emit("conference-window-ready", ())
+        .unwrap_or_else(|e| log::error!("Failed to emit window ready: {}", e));
+}
+
+/// Monitors connected now, primary first (place_window()'s fallback)
+fn monitor_areas(window: &tauri::WebviewWindow) -> Vec<MonitorArea> {
+    let primary = window.primary_monitor().ok().flatten();
+    let mut monitors: Vec<Monitor> = primary.iter().cloned().collect();
+    for monitor in window.available_monitors().unwrap_or_default() {
+        let is_primary = primary.as_ref()
+            .is_some_and(|p| p.name() == monitor.name() && p.position() == monitor.position());
+        if !is_primary {
+            monitors.push(monitor);
+        }
+    }
+    monitors.iter().map(monitor_area).collect()
+}
+
+fn monitor_area(monitor: &Monitor) -> MonitorArea {
+    MonitorArea {
+        name: monitor.name().cloned(),
+        area: Rect {
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        },
+        scale_factor: monitor.scale_factor(),
+    }
+}
+
+/// Current outer position, inner size and monitor of the window
+fn capture_geometry(window: &tauri::WebviewWindow) -> Option<WindowGeometry> {
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    let monitor = window.current_monitor().ok().flatten();
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        monitor: monitor.and_then(|m| m.name().cloned()),
+    })
+}
+
+/// Move/resize to a remembered geometry, fitted to the monitors there are now
+fn restore_geometry(window: &tauri::WebviewWindow, saved: &WindowGeometry) {
+    let Some(rect) = place_window(saved.rect(), saved.monitor.as_deref(), &monitor_areas(window)) else {
+        log::warn!("No monitors found, keeping default window position");
+        return;
+    };
+    if let Err(e) = set_rect(window, rect) {
+        log::error!("Failed to restore window geometry: {}", e);
+    }
+}
+
+fn set_rect(window: &tauri::WebviewWindow, rect: Rect) -> tauri::Result<()> {
+    window.set_size(PhysicalSize::new(rect.width, rect.height))?;
+    window.set_position(PhysicalPosition::new(rect.x, rect.y))
+}
+
+fn window_error(message: String) -> AppError {
+    AppError::Window { label: WINDOW_LABEL.to_string(), message }
+}
+
 impl Drop for ConferenceWindow {
     /// Cleanup on drop
     /// What: Ensures window is closed when manager is dropped
@@ -248,10 +660,45 @@ mod tests {
             start_with_audio_muted: false,
             start_with_video_muted: true,
             always_on_top: true,
+            target_id: "tg_secret_id".to_string(),
+            geometry: None,
         };
         
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("jc-test123"));
         assert!(json.contains("Test User"));
+        // App-side fields stay out of the page URL
+        assert!(!json.contains("tg_secret_id"));
+    }
+
+    #[test]
+    fn test_command_serialization() {
+        use serde_json::json;
+
+        let cases = [
+            (ConferenceCommand::ToggleMute, json!({ "command": "toggleAudio" })),
+            (ConferenceCommand::ToggleVideo, json!({ "command": "toggleVideo" })),
+            (ConferenceCommand::ToggleScreenShare, json!({ "command": "toggleShareScreen" })),
+            (ConferenceCommand::RaiseHand, json!({ "command": "toggleRaiseHand" })),
+            (ConferenceCommand::SetDisplayName("Alice".into()), json!({ "command": "displayName", "arg": "Alice" })),
+            (ConferenceCommand::SetSubject("Standup".into()), json!({ "command": "subject", "arg": "Standup" })),
+            (ConferenceCommand::Hangup, json!({ "command": "hangup" })),
+        ];
+        for (command, expected) in cases {
+            assert_eq!(serde_json::to_value(&command).unwrap(), expected);
+            assert_eq!(serde_json::from_value::<ConferenceCommand>(expected).unwrap(), command);
+        }
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_command_validation() {
+        assert!(ConferenceCommand::ToggleMute.validate().is_ok());
+        assert!(ConferenceCommand::SetDisplayName("Alice".into()).validate().is_ok());
+        assert!(ConferenceCommand::SetDisplayName("  ".into()).validate().is_err());
+        assert!(ConferenceCommand::SetSubject("a\nb".into()).validate().is_err());
+        assert!(ConferenceCommand::SetSubject("x".repeat(MAX_TEXT_CHARS + 1)).validate().is_err());
+        assert!(serde_json::from_value::<ConferenceCommand>(serde_json::json!({ "command": "kickParticipant" })).is_err());
     }
 }
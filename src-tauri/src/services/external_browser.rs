@@ -1,28 +1,37 @@
-// External browser service - opens meetings in system default browser
+// External browser service - opens meetings in a browser window
 // This is now the primary way to join meetings
 
+use crate::error::AppError;
+use blink::models::BrowserSettings;
+use std::process::Command;
 use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
 
 pub struct ExternalBrowserService;
 
 impl ExternalBrowserService {
-    /// Opens a meeting URL in the system's default browser
-    pub fn open_meeting(app_handle: &AppHandle, room_id: &str) -> Result<(), String> {
-        // You can easily switch to a different service here:
-        // - Daily.co: format!("https://justcall.daily.co/{}", room_id)
-        // - Whereby: format!("https://justcall.whereby.com/{}", room_id)  
-        // - Jami: format!("https://meet.jami.net/{}", room_id)
-        let url = format!("https://meet.jit.si/{}", room_id);
+    /// Opens a meeting URL in the configured browser, else the system default
+    /// The URL comes from Target::meeting_url() - a derived room on
+    /// blink::core::room::MEETING_BASE_URL, or a Link target's stored URL
+    /// Contract: If the configured browser fails to start, falls back to the
+    ///           system default (xdg-open on Linux) with a warning
+    pub fn open_meeting(app_handle: &AppHandle, url: &str, browser: &BrowserSettings) -> Result<(), AppError> {
+        // Link URLs often carry a password (?pwd=...), keep it out of the log
+        log::info!("Opening meeting in external browser: {}", url.split(['?', '#']).next().unwrap_or(url));
         
-        log::info!("Opening meeting in external browser: {}", url);
+        if let Some(command) = browser.launch_command(url) {
+            match Command::new(&command[0]).args(&command[1..]).spawn() {
+                Ok(_) => return Ok(()),
+                Err(e) => log::warn!("Failed to launch {} ({}), using the default browser", command[0], e),
+            }
+        }
         
         // In Tauri v2, we use the shell plugin's open command
         app_handle
             .shell()
-            .open(&url, None)
-            .map_err(|e| format!("Failed to open browser: {}", e))?;
+            .open(url, None)
+            .map_err(|e| AppError::Provider { message: format!("Failed to open browser: {}", e) })?;
         
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -2,20 +2,29 @@
 // What: Manages system-wide keyboard shortcuts for the application
 // Why: Provides a clean interface for registering and handling hotkeys
 // Used by: lib.rs (app setup), commands.rs (settings updates)
-// Calls: tauri-plugin-global-shortcut API
+// Calls: tauri-plugin-global-shortcut API, notifier (registration failures)
 // Events: Emits "hotkey-pressed" events
 // Change notes: Uses Tauri v2 global shortcut plugin
 
+use crate::error::AppError;
+use crate::services::notifier::{self, Notification};
 use std::collections::HashMap;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ShortcutAction {
     JoinPrimary,
     JoinTarget { id: String },
+    /// Named channel of a target, by channel id
+    JoinChannel { id: String },
     Hangup,
+    /// In-call toggles (embedded join mode)
+    ToggleMute,
+    ToggleVideo,
+    /// Conference window to/from the compact always-on-top mini mode
+    ToggleMiniMode,
 }
 
 pub struct GlobalShortcutService {
@@ -42,21 +51,30 @@ impl GlobalShortcutService {
     /// Contract:
     /// - hotkey: Format like "Cmd+Opt+J" or "Ctrl+Alt+H"
     /// - action: What to do when hotkey is pressed
-    /// - Returns error if hotkey is invalid or conflicts
+    /// - Returns HotkeyParse if invalid, HotkeyConflict if bound to a different
+    ///   action here or held by another application
     /// Used by: setup_default_hotkeys(), update_hotkeys command
     /// Calls: tauri-plugin-global-shortcut register API
     /// Change notes: Updated for Tauri v2 plugin API
-    pub fn register_hotkey(&mut self, hotkey: &str, action: ShortcutAction) -> Result<(), String> {
+    pub fn register_hotkey(&mut self, hotkey: &str, action: ShortcutAction) -> Result<(), AppError> {
         log::info!("Registering hotkey: {} -> {:?}", hotkey, action);
         
         // Parse the shortcut string
-        let shortcut = hotkey.parse::<Shortcut>()
-            .map_err(|e| format!("Invalid hotkey format '{}': {}", hotkey, e))?;
+        let shortcut = parse_hotkey(hotkey)?;
         
         // Check if already registered
-        if self.shortcuts.contains_key(hotkey) {
-            log::warn!("Hotkey {} already registered, updating action", hotkey);
-            self.unregister_hotkey(hotkey)?;
+        match self.shortcuts.get(hotkey) {
+            Some(existing) if *existing != action => {
+                return Err(AppError::HotkeyConflict {
+                    hotkey: hotkey.to_string(),
+                    message: format!("already bound to {:?}", existing),
+                });
+            }
+            Some(_) => {
+                log::warn!("Hotkey {} already registered, re-registering", hotkey);
+                self.unregister_hotkey(hotkey)?;
+            }
+            None => {}
         }
         
         // Clone values for the closure
@@ -80,13 +98,19 @@ impl GlobalShortcutService {
                         ShortcutAction::JoinTarget { id } => {
                             log::info!("Join target {} requested", id);
                         }
+                        ShortcutAction::JoinChannel { id } => {
+                            log::info!("Join channel {} requested", id);
+                        }
                         ShortcutAction::Hangup => {
                             log::info!("Hangup requested");
                         }
+                        ShortcutAction::ToggleMute | ShortcutAction::ToggleVideo | ShortcutAction::ToggleMiniMode => {
+                            log::info!("{:?} requested", action_clone);
+                        }
                     }
                 }
             })
-            .map_err(|e| format!("Failed to register hotkey: {}", e))?;
+            .map_err(|e| registration_error(hotkey, e))?;
         
         self.shortcuts.insert(hotkey.to_string(), action);
         log::info!("Successfully registered hotkey: {}", hotkey);
@@ -98,16 +122,15 @@ impl GlobalShortcutService {
     /// Why: Needed when updating hotkeys or cleaning up
     /// Used by: register_hotkey (for updates), drop impl
     /// Calls: tauri-plugin-global-shortcut unregister API
-    pub fn unregister_hotkey(&mut self, hotkey: &str) -> Result<(), String> {
+    pub fn unregister_hotkey(&mut self, hotkey: &str) -> Result<(), AppError> {
         log::info!("Unregistering hotkey: {}", hotkey);
         
         // Parse the shortcut string
-        let shortcut = hotkey.parse::<Shortcut>()
-            .map_err(|e| format!("Invalid hotkey format: {}", e))?;
+        let shortcut = parse_hotkey(hotkey)?;
         
         self.app_handle.global_shortcut()
             .unregister(shortcut)
-            .map_err(|e| format!("Failed to unregister hotkey: {}", e))?;
+            .map_err(|e| AppError::HotkeyRegistration { hotkey: hotkey.to_string(), message: e.to_string() })?;
         
         self.shortcuts.remove(hotkey);
         Ok(())
@@ -118,7 +141,7 @@ impl GlobalShortcutService {
     /// Why: Cleanup on shutdown or when resetting all hotkeys
     /// Used by: drop impl, reset command
     /// Calls: unregister for each hotkey
-    pub fn unregister_all(&mut self) -> Result<(), String> {
+    pub fn unregister_all(&mut self) -> Result<(), AppError> {
         log::info!("Unregistering all hotkeys");
         
         let hotkeys: Vec<String> = self.shortcuts.keys().cloned().collect();
@@ -133,26 +156,57 @@ impl GlobalShortcutService {
     }
     
     /// Setup default hotkeys from settings
-    /// What: Registers the default join/hangup hotkeys
+    /// What: Registers the join/hangup hotkeys, then mute/video, per-target and per-channel ones
     /// Why: Called on app startup to enable hotkeys
-    /// Used by: App setup after loading settings
+    /// Used by: App setup after loading settings, commands after keybinds change
     /// Calls: register_hotkey
-    pub fn setup_default_hotkeys(&mut self, keybinds: &blink::models::settings::Keybinds) -> Result<(), String> {
+    /// Contract:
+    ///   - Join/hangup failures are returned
+    ///   - A failing mute/video/target/channel hotkey is logged and skipped so
+    ///     one bad binding doesn't disable the rest
+    ///   - Every failure is also a HotkeyFailed notification (AppState must be managed)
+    pub fn setup_default_hotkeys(&mut self, keybinds: &blink::models::settings::Keybinds) -> Result<(), AppError> {
         log::info!("Setting up default hotkeys");
         
         // Register join primary
         if !keybinds.join_primary.is_empty() {
-            self.register_hotkey(&keybinds.join_primary, ShortcutAction::JoinPrimary)?;
+            self.register_hotkey(&keybinds.join_primary, ShortcutAction::JoinPrimary)
+                .inspect_err(|e| self.notify_failure(e))?;
         }
         
         // Register hangup
         if !keybinds.hangup.is_empty() {
-            self.register_hotkey(&keybinds.hangup, ShortcutAction::Hangup)?;
+            self.register_hotkey(&keybinds.hangup, ShortcutAction::Hangup)
+                .inspect_err(|e| self.notify_failure(e))?;
+        }
+        
+        let in_call = [
+            (keybinds.toggle_mute.as_ref(), ShortcutAction::ToggleMute),
+            (keybinds.toggle_video.as_ref(), ShortcutAction::ToggleVideo),
+            (keybinds.toggle_mini_mode.as_ref(), ShortcutAction::ToggleMiniMode),
+        ];
+        let in_call = in_call.into_iter().filter_map(|(hotkey, action)| hotkey.map(|h| (h, action)));
+        let per_target = keybinds.target_hotkeys.iter()
+            .map(|(id, hotkey)| (hotkey, ShortcutAction::JoinTarget { id: id.clone() }));
+        let per_channel = keybinds.channel_hotkeys.iter()
+            .map(|(id, hotkey)| (hotkey, ShortcutAction::JoinChannel { id: id.clone() }));
+        for (hotkey, action) in in_call.chain(per_target).chain(per_channel) {
+            if hotkey.is_empty() {
+                continue;
+            }
+            if let Err(e) = self.register_hotkey(hotkey, action) {
+                log::error!("Skipping hotkey: {}", e);
+                self.notify_failure(&e);
+            }
         }
         
         Ok(())
     }
     
+    fn notify_failure(&self, error: &AppError) {
+        notifier::notify(&self.app_handle, Notification::hotkey_failed(error));
+    }
+    
     /// Check if a hotkey is already registered
     /// What: Checks if a hotkey string is in use
     /// Why: Prevents conflicts when adding new hotkeys
@@ -170,6 +224,26 @@ impl GlobalShortcutService {
     }
 }
 
+/// parse_hotkey(hotkey)
+/// What: Parses a hotkey string into the plugin's Shortcut
+/// Used by: register/unregister, validate_hotkey command
+pub fn parse_hotkey(hotkey: &str) -> Result<Shortcut, AppError> {
+    hotkey.parse::<Shortcut>().map_err(|e| AppError::HotkeyParse {
+        hotkey: hotkey.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Map a plugin registration failure; "already registered" means another app holds it
+fn registration_error(hotkey: &str, error: impl std::fmt::Display) -> AppError {
+    let message = error.to_string();
+    if message.to_lowercase().contains("already registered") {
+        AppError::HotkeyConflict { hotkey: hotkey.to_string(), message }
+    } else {
+        AppError::HotkeyRegistration { hotkey: hotkey.to_string(), message }
+    }
+}
+
 impl Drop for GlobalShortcutService {
     /// Cleanup on drop
     /// What: Unregisters all hotkeys when service is dropped
@@ -195,4 +269,19 @@ mod tests {
             _ => panic!("Wrong action type"),
         }
     }
+    
+    #[test]
+    fn test_parse_hotkey_error() {
+        assert!(parse_hotkey("Ctrl+Alt+J").is_ok());
+        
+        let error = parse_hotkey("Ctrl+").unwrap_err();
+        assert_eq!(error.code(), "hotkey_parse");
+        assert_eq!(error.details()["hotkey"], "Ctrl+");
+    }
+    
+    #[test]
+    fn test_registration_error_classification() {
+        assert_eq!(registration_error("Ctrl+J", "HotKey already registered: HotKey { .. }").code(), "hotkey_conflict");
+        assert_eq!(registration_error("Ctrl+J", "Unable to register hotkey: X error").code(), "hotkey_registration");
+    }
 }
\ No newline at end of file
@@ -5,4 +5,9 @@
 
 pub mod global_shortcuts;
 pub mod conference_window;
+pub mod conference_events;
 pub mod external_browser;
+pub mod tray_menu;
+pub mod tray_icon;
+pub mod window_manager;
+pub mod notifier;
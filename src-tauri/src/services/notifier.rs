@@ -0,0 +1,265 @@
+// Notifier
+// What: Desktop notifications for join failures, a missing primary target,
+//       hotkey registration failures and people joining the call
+// Why: Hotkey and tray actions have no window to show an error in; without a
+//      popup a failed join just looks like nothing happened
+// Used by: lib.rs (joins, hotkey setup), GlobalShortcutService, CallController
+// Calls: A NotificationBackend (freedesktop D-Bus on Linux)
+// Change notes: Gated by AppSettings::notifies() (show_notifications plus the
+//               per-event toggles); backends never see suppressed notifications
+
+use crate::error::AppError;
+use crate::state::AppState;
+use blink::models::settings::AppSettings;
+use blink::models::NotificationKind;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// A notification to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+}
+
+impl Notification {
+    pub fn join_failed(error: &AppError) -> Self {
+        Self {
+            kind: NotificationKind::JoinFailed,
+            title: "Couldn't join the call".to_string(),
+            body: error.to_string(),
+        }
+    }
+
+    pub fn no_primary_target() -> Self {
+        Self {
+            kind: NotificationKind::NoPrimaryTarget,
+            title: "No primary target".to_string(),
+            body: "Pick a primary target in Settings to join it with the hotkey.".to_string(),
+        }
+    }
+
+    pub fn hotkey_failed(error: &AppError) -> Self {
+        Self {
+            kind: NotificationKind::HotkeyFailed,
+            title: "Hotkey not available".to_string(),
+            body: error.to_string(),
+        }
+    }
+
+    pub fn partner_joined(display_name: Option<&str>) -> Self {
+        Self {
+            kind: NotificationKind::PartnerJoined,
+            title: format!("{} joined the call", display_name.unwrap_or("Someone")),
+            body: String::new(),
+        }
+    }
+
+    /// Freedesktop icon name
+    fn icon(&self) -> &'static str {
+        match self.kind {
+            NotificationKind::PartnerJoined => "call-start",
+            _ => "dialog-warning",
+        }
+    }
+}
+
+/// Shows notifications on the desktop
+/// Contract: Send + Sync; show() may block briefly (callers go through notify())
+pub trait NotificationBackend: Send + Sync {
+    fn show(&self, notification: &Notification) -> Result<(), AppError>;
+}
+
+/// org.freedesktop.Notifications over the session bus
+#[cfg(target_os = "linux")]
+pub struct FreedesktopBackend;
+
+#[cfg(target_os = "linux")]
+impl FreedesktopBackend {
+    /// How long to wait for the notification daemon
+    const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+}
+
+#[cfg(target_os = "linux")]
+impl NotificationBackend for FreedesktopBackend {
+    fn show(&self, notification: &Notification) -> Result<(), AppError> {
+        let error = |e: dbus::Error| AppError::Notification {
+            message: format!("Notification daemon unavailable: {}", e),
+        };
+        let connection = dbus::blocking::Connection::new_session().map_err(error)?;
+        let proxy = connection.with_proxy(
+            "org.freedesktop.Notifications",
+            "/org/freedesktop/Notifications",
+            Self::TIMEOUT,
+        );
+        // Notify(app_name, replaces_id, app_icon, summary, body, actions, hints, expire_timeout)
+        let (_id,): (u32,) = proxy
+            .method_call(
+                "org.freedesktop.Notifications",
+                "Notify",
+                (
+                    "Blink",
+                    0u32,
+                    notification.icon(),
+                    notification.title.as_str(),
+                    notification.body.as_str(),
+                    Vec::<String>::new(),
+                    dbus::arg::PropMap::new(),
+                    -1i32,
+                ),
+            )
+            .map_err(error)?;
+        Ok(())
+    }
+}
+
+/// Logs instead of notifying (platforms without a backend yet)
+#[cfg(not(target_os = "linux"))]
+pub struct LogBackend;
+
+#[cfg(not(target_os = "linux"))]
+impl NotificationBackend for LogBackend {
+    fn show(&self, notification: &Notification) -> Result<(), AppError> {
+        log::info!("Notification: {} {}", notification.title, notification.body);
+        Ok(())
+    }
+}
+
+/// Records notifications instead of showing them
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryBackend {
+    shown: std::sync::Mutex<Vec<Notification>>,
+}
+
+#[cfg(test)]
+impl MemoryBackend {
+    pub fn shown(&self) -> Vec<Notification> {
+        self.shown.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl NotificationBackend for MemoryBackend {
+    fn show(&self, notification: &Notification) -> Result<(), AppError> {
+        self.shown.lock().unwrap().push(notification.clone());
+        Ok(())
+    }
+}
+
+/// A shared backend (tests keep a handle to inspect it)
+impl<T: NotificationBackend + ?Sized> NotificationBackend for std::sync::Arc<T> {
+    fn show(&self, notification: &Notification) -> Result<(), AppError> {
+        (**self).show(notification)
+    }
+}
+
+pub struct Notifier {
+    backend: Box<dyn NotificationBackend>,
+}
+
+impl Notifier {
+    pub fn new(backend: Box<dyn NotificationBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// The platform's backend
+    pub fn system() -> Self {
+        #[cfg(target_os = "linux")]
+        let backend = FreedesktopBackend;
+        #[cfg(not(target_os = "linux"))]
+        let backend = LogBackend;
+        Self::new(Box::new(backend))
+    }
+
+    /// send(settings, notification)
+    /// What: Shows the notification if the settings allow its kind
+    /// Contract: Returns whether it was shown; backend failures are logged
+    pub fn send(&self, settings: &AppSettings, notification: &Notification) -> bool {
+        if !settings.notifies(notification.kind) {
+            log::debug!("Notification {:?} suppressed by settings", notification.kind);
+            return false;
+        }
+        match self.backend.show(notification) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Failed to show notification: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// notify(app, notification)
+/// What: Sends through AppState's notifier on a background thread
+/// Why: Callers may hold the call controller lock or run on the main thread;
+///      the settings lookup and the D-Bus round trip happen off both
+/// Contract: AppState must be managed before the thread runs (after setup's
+///           app.manage); never fails the caller
+pub fn notify<R: Runtime>(app: &AppHandle<R>, notification: Notification) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let Some(state) = app.try_state::<AppState>() else {
+            log::warn!("Notification before app setup: {}", notification.title);
+            return;
+        };
+        let settings = state.settings_store.lock().unwrap().settings().app_settings.clone();
+        state.notifier.send(&settings, &notification);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn memory_notifier() -> (Notifier, Arc<MemoryBackend>) {
+        let backend = Arc::new(MemoryBackend::default());
+        (Notifier::new(Box::new(backend.clone())), backend)
+    }
+
+    #[test]
+    fn test_send_when_enabled() {
+        let (notifier, backend) = memory_notifier();
+        let settings = AppSettings::default();
+
+        assert!(notifier.send(&settings, &Notification::partner_joined(Some("Alice"))));
+        assert!(notifier.send(&settings, &Notification::no_primary_target()));
+
+        let shown = backend.shown();
+        assert_eq!(shown.len(), 2);
+        assert_eq!(shown[0].title, "Alice joined the call");
+        assert_eq!(shown[1].kind, NotificationKind::NoPrimaryTarget);
+    }
+
+    #[test]
+    fn test_error_notifications() {
+        let error = AppError::Provider { message: "No browser found".into() };
+        let notification = Notification::join_failed(&error);
+        assert_eq!(notification.kind, NotificationKind::JoinFailed);
+        assert_eq!(notification.body, "No browser found");
+        assert_eq!(notification.icon(), "dialog-warning");
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_respects_settings() {
+        let (notifier, backend) = memory_notifier();
+        let mut settings = AppSettings::default();
+
+        settings.notifications.partner_joined = false;
+        assert!(!notifier.send(&settings, &Notification::partner_joined(None)));
+
+        settings.notifications.partner_joined = true;
+        settings.show_notifications = false;
+        assert!(!notifier.send(&settings, &Notification::partner_joined(None)));
+
+        assert!(backend.shown().is_empty());
+    }
+
+    #[test]
+    fn test_partner_without_name() {
+        assert_eq!(Notification::partner_joined(None).title, "Someone joined the call");
+    }
+}
@@ -0,0 +1,162 @@
+// Tray icon
+// What: The tray image for each call state (icons/tray-icon.png with a colored
+//       status dot) and the action of a left-click on it
+// Why: Whether a call is connecting, running or muted should be visible in the
+//      panel without opening the menu
+// Used by: tray_menu::refresh(), lib.rs (tray setup and left-click)
+// Calls: tauri::include_image! (decodes the PNG at compile time)
+// Change notes: Variants are drawn at runtime from the one embedded image, so
+//               a new tray-icon.png needs no other assets
+
+use crate::services::global_shortcuts::ShortcutAction;
+use blink::core::CallState;
+use blink::models::TrayClickAction;
+use tauri::image::Image;
+
+/// Status dot colors (RGB)
+const CONNECTING_COLOR: [u8; 3] = [245, 158, 11];
+const IN_CALL_COLOR: [u8; 3] = [34, 197, 94];
+const MUTED_COLOR: [u8; 3] = [239, 68, 68];
+/// Ring around the dot so it stands out on dark and light panels
+const OUTLINE_COLOR: [u8; 3] = [255, 255, 255];
+
+/// What the tray icon shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayIconState {
+    Idle,
+    Connecting,
+    InCall,
+    Muted,
+}
+
+impl TrayIconState {
+    /// from_call(state, muted)
+    /// Contract: Disconnecting shows as Connecting (the call is in transition);
+    ///           muted only matters while in the call
+    pub fn from_call(state: CallState, muted: bool) -> Self {
+        match state {
+            CallState::Idle => TrayIconState::Idle,
+            CallState::Connecting | CallState::Disconnecting => TrayIconState::Connecting,
+            CallState::InCall if muted => TrayIconState::Muted,
+            CallState::InCall => TrayIconState::InCall,
+        }
+    }
+
+    fn dot_color(self) -> Option<[u8; 3]> {
+        match self {
+            TrayIconState::Idle => None,
+            TrayIconState::Connecting => Some(CONNECTING_COLOR),
+            TrayIconState::InCall => Some(IN_CALL_COLOR),
+            TrayIconState::Muted => Some(MUTED_COLOR),
+        }
+    }
+}
+
+/// icon(state)
+/// What: The tray image for `state`: the plain icon when idle, otherwise the
+///       icon with a status dot in the bottom-right corner
+pub fn icon(state: TrayIconState) -> Image<'static> {
+    let base = tauri::include_image!("icons/tray-icon.png");
+    match state.dot_color() {
+        None => base.to_owned(),
+        Some(color) => {
+            let (width, height) = (base.width(), base.height());
+            let mut rgba = base.rgba().to_vec();
+            overlay(&mut rgba, width, height, color);
+            Image::new_owned(rgba, width, height)
+        }
+    }
+}
+
+/// overlay(rgba, width, height, color)
+/// What: Paints an opaque, outlined dot over the bottom-right corner
+/// Contract: The dot's diameter is 60% of the shorter side; buffers smaller
+///           than width * height * 4 are left untouched
+pub fn overlay(rgba: &mut [u8], width: u32, height: u32, color: [u8; 3]) {
+    let (w, h) = (width as usize, height as usize);
+    if w == 0 || h == 0 || rgba.len() < w * h * 4 {
+        return;
+    }
+    let radius = width.min(height) as f32 * 0.3;
+    let outline = (radius / 5.0).max(1.0);
+    let (cx, cy) = (width as f32 - radius, height as f32 - radius);
+
+    for y in 0..h {
+        for x in 0..w {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            let distance = (dx * dx + dy * dy).sqrt();
+            let rgb = if distance <= radius - outline {
+                color
+            } else if distance <= radius {
+                OUTLINE_COLOR
+            } else {
+                continue;
+            };
+            let pixel = (y * w + x) * 4;
+            rgba[pixel..pixel + 3].copy_from_slice(&rgb);
+            rgba[pixel + 3] = 255;
+        }
+    }
+}
+
+/// left_click_action(setting)
+/// What: The hotkey action a left-click runs; None means open the settings
+/// Used by: lib.rs on_tray_icon_event
+pub fn left_click_action(setting: TrayClickAction) -> Option<ShortcutAction> {
+    match setting {
+        TrayClickAction::JoinPrimary => Some(ShortcutAction::JoinPrimary),
+        TrayClickAction::Hangup => Some(ShortcutAction::Hangup),
+        TrayClickAction::OpenSettings => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(rgba: &[u8], width: u32, x: usize, y: usize) -> &[u8] {
+        let i = (y * width as usize + x) * 4;
+        &rgba[i..i + 4]
+    }
+
+    #[test]
+    fn test_state_from_call() {
+        assert_eq!(TrayIconState::from_call(CallState::Idle, false), TrayIconState::Idle);
+        assert_eq!(TrayIconState::from_call(CallState::Connecting, false), TrayIconState::Connecting);
+        assert_eq!(TrayIconState::from_call(CallState::InCall, false), TrayIconState::InCall);
+        assert_eq!(TrayIconState::from_call(CallState::InCall, true), TrayIconState::Muted);
+    }
+
+    #[test]
+    fn test_overlay_draws_outlined_dot() {
+        let mut rgba = vec![0u8; 32 * 32 * 4];
+        overlay(&mut rgba, 32, 32, MUTED_COLOR);
+
+        assert_eq!(pixel(&rgba, 32, 22, 22), [239, 68, 68, 255]);
+        assert_eq!(pixel(&rgba, 32, 22, 13), [255, 255, 255, 255]);
+        assert_eq!(pixel(&rgba, 32, 0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&rgba, 32, 10, 22), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_left_click_action() {
+        assert_eq!(left_click_action(TrayClickAction::JoinPrimary), Some(ShortcutAction::JoinPrimary));
+        assert_eq!(left_click_action(TrayClickAction::Hangup), Some(ShortcutAction::Hangup));
+        assert_eq!(left_click_action(TrayClickAction::OpenSettings), None);
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_muted_ignored_outside_call() {
+        assert_eq!(TrayIconState::from_call(CallState::Idle, true), TrayIconState::Idle);
+        assert_eq!(TrayIconState::from_call(CallState::Disconnecting, true), TrayIconState::Connecting);
+    }
+
+    #[test]
+    fn test_overlay_short_buffer_untouched() {
+        let mut rgba = vec![0u8; 16];
+        overlay(&mut rgba, 32, 32, IN_CALL_COLOR);
+        assert!(rgba.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,254 @@
+// Tray menu
+// What: Builds the tray menu (Join and Recent submenus, in-call actions,
+//       Settings, Quit), tooltip and icon from the settings and call state
+// Why: Targets and their named channels must be joinable without a hotkey, and
+//      the tray should show whether (and with whom) a call is running
+// Used by: lib.rs (tray setup, menu events, call-state changes),
+//          commands.rs (refresh after target changes)
+// Calls: tauri menu API, AppState settings store and call controller, tray_icon
+// Change notes: Item ids are parsed back by menu_action(); change both together
+
+use crate::services::global_shortcuts::ShortcutAction;
+use crate::services::tray_icon::{self, TrayIconState};
+use crate::state::AppState;
+use blink::core::CallState;
+use blink::models::Target;
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Id of the app's tray icon
+pub const TRAY_ID: &str = "main";
+
+const JOIN_TARGET_PREFIX: &str = "join-target:";
+const JOIN_CHANNEL_PREFIX: &str = "join-channel:";
+// Recent entries get their own ids - menu ids should be unique
+const RECENT_TARGET_PREFIX: &str = "recent-target:";
+const RECENT_CHANNEL_PREFIX: &str = "recent-channel:";
+const HANGUP_ID: &str = "hangup";
+const TOGGLE_MUTE_ID: &str = "toggle-mute";
+const TOGGLE_VIDEO_ID: &str = "toggle-video";
+
+/// Marks the primary target in the Join submenu
+const PRIMARY_MARK: &str = "★ ";
+
+/// Recent submenu entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentItem {
+    pub target_id: String,
+    pub channel_id: Option<String>,
+    pub label: String,
+}
+
+/// Everything the tray shows
+/// Used by: build_menu(), tooltip(), refresh()
+#[derive(Debug, Clone, Default)]
+pub struct TrayModel {
+    pub targets: Vec<Target>,
+    pub recent: Vec<RecentItem>,
+    pub call_state: CallState,
+    /// Microphone muted in the current embedded call
+    pub muted: bool,
+    /// Label of the target of the current embedded call
+    pub call_label: Option<String>,
+}
+
+impl TrayModel {
+    /// current(app)
+    /// What: Snapshot of the settings store and call controller
+    /// Contract: Locks settings_store and call_controller one after the other,
+    ///           never both - callers must not hold either
+    pub fn current<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let state = app.state::<AppState>();
+        let (targets, recent) = {
+            let store = state.settings_store.lock().unwrap();
+            let recent = store.recent_calls().into_iter()
+                .map(|(target, channel)| RecentItem {
+                    target_id: target.id.clone(),
+                    channel_id: channel.map(|c| c.id.clone()),
+                    label: match channel {
+                        Some(channel) => format!("{} › {}", target.label, channel.name),
+                        None => target.label.clone(),
+                    },
+                })
+                .collect();
+            (store.get_targets().to_vec(), recent)
+        };
+        let (call_state, call_target, muted) = {
+            let controller = state.call_controller.lock().unwrap();
+            (controller.state(), controller.target_id(), controller.is_muted())
+        };
+        let call_label = call_target
+            .and_then(|id| targets.iter().find(|t| t.id == id).map(|t| t.label.clone()));
+
+        Self { targets, recent, call_state, muted, call_label }
+    }
+
+    /// Which tray image to show
+    pub fn icon_state(&self) -> TrayIconState {
+        TrayIconState::from_call(self.call_state, self.muted)
+    }
+}
+
+/// build_menu(manager, model)
+/// What: Join (targets, primary marked, then channels), Recent, Hang up /
+///       Mute / Camera (enabled only in a call), Settings, Quit
+/// Used by: lib.rs tray setup, refresh()
+pub fn build_menu<R: Runtime, M: Manager<R>>(manager: &M, model: &TrayModel) -> tauri::Result<Menu<R>> {
+    let mut join_items = Vec::new();
+    for target in &model.targets {
+        let label = if target.is_primary {
+            format!("{}{}", PRIMARY_MARK, target.label)
+        } else {
+            target.label.clone()
+        };
+        join_items.push(MenuItem::with_id(
+            manager,
+            format!("{}{}", JOIN_TARGET_PREFIX, target.id),
+            label,
+            true,
+            None::<&str>,
+        )?);
+        for channel in &target.channels {
+            join_items.push(MenuItem::with_id(
+                manager,
+                format!("{}{}", JOIN_CHANNEL_PREFIX, channel.id),
+                format!("{} › {}", target.label, channel.name),
+                true,
+                None::<&str>,
+            )?);
+        }
+    }
+    if join_items.is_empty() {
+        join_items.push(MenuItem::with_id(manager, "join-none", "No targets yet", false, None::<&str>)?);
+    }
+    let join_menu = submenu(manager, "Join", &join_items)?;
+
+    let mut recent_items = Vec::new();
+    for recent in &model.recent {
+        let id = match &recent.channel_id {
+            Some(channel_id) => format!("{}{}", RECENT_CHANNEL_PREFIX, channel_id),
+            None => format!("{}{}", RECENT_TARGET_PREFIX, recent.target_id),
+        };
+        recent_items.push(MenuItem::with_id(manager, id, &recent.label, true, None::<&str>)?);
+    }
+    if recent_items.is_empty() {
+        recent_items.push(MenuItem::with_id(manager, "recent-none", "No recent calls", false, None::<&str>)?);
+    }
+    let recent_menu = submenu(manager, "Recent", &recent_items)?;
+
+    let busy = model.call_state.is_busy();
+    let in_call = model.call_state == CallState::InCall;
+    let hangup_i = MenuItem::with_id(manager, HANGUP_ID, "Hang up", busy, None::<&str>)?;
+    let mute_label = if model.muted { "Unmute" } else { "Mute" };
+    let mute_i = MenuItem::with_id(manager, TOGGLE_MUTE_ID, mute_label, in_call, None::<&str>)?;
+    let video_i = MenuItem::with_id(manager, TOGGLE_VIDEO_ID, "Camera on / off", in_call, None::<&str>)?;
+
+    let separator = PredefinedMenuItem::separator(manager)?;
+    let separator2 = PredefinedMenuItem::separator(manager)?;
+    let settings_i = MenuItem::with_id(manager, "settings", "Settings", true, None::<&str>)?;
+    let quit_i = MenuItem::with_id(manager, "quit", "Quit", true, None::<&str>)?;
+
+    Menu::with_items(manager, &[
+        &join_menu, &recent_menu, &separator,
+        &hangup_i, &mute_i, &video_i, &separator2,
+        &settings_i, &quit_i,
+    ])
+}
+
+fn submenu<R: Runtime, M: Manager<R>>(manager: &M, title: &str, items: &[MenuItem<R>]) -> tauri::Result<Submenu<R>> {
+    let refs: Vec<&dyn IsMenuItem<R>> = items.iter().map(|item| item as &dyn IsMenuItem<R>).collect();
+    Submenu::with_items(manager, title, true, &refs)
+}
+
+/// tooltip(model)
+/// What: "Blink - ready", "Blink - in call (Alice)"
+pub fn tooltip(model: &TrayModel) -> String {
+    match &model.call_label {
+        Some(label) if model.call_state.is_busy() => format!("Blink - {} ({})", model.call_state, label),
+        _ => format!("Blink - {}", model.call_state),
+    }
+}
+
+/// refresh(app)
+/// What: Rebuilds the tray menu, tooltip and icon from the settings store and call state
+/// Why: Added/renamed/removed targets, new recent calls and call-state changes
+///      must show up immediately
+/// Used by: commands.rs after target, channel and settings changes; lib.rs
+///          after joins and on "call-state-changed" / "mute-changed"
+/// Contract: Never fails the caller; problems are logged. Callers must not
+///           hold the settings_store or call_controller lock (TrayModel::current)
+pub fn refresh<R: Runtime>(app: &AppHandle<R>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let model = TrayModel::current(app);
+
+    match build_menu(app, &model) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                log::error!("Failed to update tray menu: {}", e);
+            }
+        }
+        Err(e) => log::error!("Failed to build tray menu: {}", e),
+    }
+    if let Err(e) = tray.set_tooltip(Some(tooltip(&model))) {
+        log::error!("Failed to update tray tooltip: {}", e);
+    }
+    if let Err(e) = tray.set_icon(Some(tray_icon::icon(model.icon_state()))) {
+        log::error!("Failed to update tray icon: {}", e);
+    }
+}
+
+/// menu_action(id)
+/// What: Maps a Join/Recent/in-call item id back to the action it triggers
+/// Used by: lib.rs tray on_menu_event
+pub fn menu_action(id: &str) -> Option<ShortcutAction> {
+    match id {
+        HANGUP_ID => return Some(ShortcutAction::Hangup),
+        TOGGLE_MUTE_ID => return Some(ShortcutAction::ToggleMute),
+        TOGGLE_VIDEO_ID => return Some(ShortcutAction::ToggleVideo),
+        _ => {}
+    }
+    if let Some(target_id) = id.strip_prefix(JOIN_TARGET_PREFIX).or_else(|| id.strip_prefix(RECENT_TARGET_PREFIX)) {
+        Some(ShortcutAction::JoinTarget { id: target_id.to_string() })
+    } else {
+        id.strip_prefix(JOIN_CHANNEL_PREFIX)
+            .or_else(|| id.strip_prefix(RECENT_CHANNEL_PREFIX))
+            .map(|channel_id| ShortcutAction::JoinChannel { id: channel_id.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_action() {
+        assert_eq!(menu_action("join-target:tg_abc"), Some(ShortcutAction::JoinTarget { id: "tg_abc".into() }));
+        assert_eq!(menu_action("join-channel:ch_xyz"), Some(ShortcutAction::JoinChannel { id: "ch_xyz".into() }));
+        assert_eq!(menu_action("recent-target:tg_abc"), Some(ShortcutAction::JoinTarget { id: "tg_abc".into() }));
+        assert_eq!(menu_action("recent-channel:ch_xyz"), Some(ShortcutAction::JoinChannel { id: "ch_xyz".into() }));
+        assert_eq!(menu_action("hangup"), Some(ShortcutAction::Hangup));
+        assert_eq!(menu_action("toggle-mute"), Some(ShortcutAction::ToggleMute));
+        assert_eq!(menu_action("toggle-video"), Some(ShortcutAction::ToggleVideo));
+    }
+
+    #[test]
+    fn test_tooltip() {
+        let mut model = TrayModel::default();
+        assert_eq!(tooltip(&model), "Blink - ready");
+
+        model.call_state = CallState::InCall;
+        model.call_label = Some("Alice".into());
+        assert_eq!(tooltip(&model), "Blink - in call (Alice)");
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_menu_action_ignores_other_items() {
+        assert_eq!(menu_action("settings"), None);
+        assert_eq!(menu_action("join-none"), None);
+        assert_eq!(menu_action("recent-none"), None);
+    }
+}
@@ -0,0 +1,135 @@
+// Window manager
+// What: Opens and focuses the app's windows (settings, welcome, conference)
+// Why: One place enforces single instances and turns build failures into
+//      AppErrors instead of panics; tray, hotkeys and commands all go through it
+// Used by: lib.rs (tray, first run), commands::open_window, CallController
+// Calls: Tauri WebviewWindowBuilder
+// Change notes: The conference window is built by ConferenceWindow (it carries
+//               call state); here it can only be focused
+
+use crate::error::AppError;
+use crate::services::conference_window;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// The app's window kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowKind {
+    Settings,
+    /// First-run onboarding (settings.html in welcome mode)
+    Welcome,
+    Conference,
+}
+
+impl WindowKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            WindowKind::Settings => "settings",
+            WindowKind::Welcome => "welcome",
+            WindowKind::Conference => conference_window::WINDOW_LABEL,
+        }
+    }
+
+    /// Windows that must not be open at the same time as this one
+    /// Why: Settings and welcome both edit the settings; the second would
+    ///      overwrite the first's changes on save
+    fn exclusive_with(self) -> &'static [WindowKind] {
+        match self {
+            WindowKind::Settings => &[WindowKind::Welcome],
+            WindowKind::Welcome => &[WindowKind::Settings],
+            WindowKind::Conference => &[],
+        }
+    }
+}
+
+pub struct WindowManager;
+
+impl WindowManager {
+    /// show(app, kind)
+    /// What: Focuses the window of this kind (or one it's exclusive with) if
+    ///       open, otherwise builds it
+    /// Contract:
+    ///   - At most one window per kind; settings and welcome never both
+    ///   - Returns the window that ended up focused
+    ///   - Conference: only an existing window is focused; Window error if
+    ///     there's no call window (calls start through CallController)
+    pub fn show<R: Runtime>(app: &AppHandle<R>, kind: WindowKind) -> Result<WebviewWindow<R>, AppError> {
+        let open = std::iter::once(kind).chain(kind.exclusive_with().iter().copied())
+            .find_map(|k| app.get_webview_window(k.label()));
+        if let Some(window) = open {
+            if window.label() != kind.label() {
+                log::info!("{} window is open, focusing it instead of {}", window.label(), kind.label());
+            }
+            focus(&window);
+            return Ok(window);
+        }
+
+        let builder = match kind {
+            WindowKind::Settings => {
+                WebviewWindowBuilder::new(app, kind.label(), WebviewUrl::App("settings.html".into()))
+                    .title("Blink Settings")
+                    .inner_size(700.0, 600.0)
+            }
+            WindowKind::Welcome => {
+                WebviewWindowBuilder::new(app, kind.label(), WebviewUrl::App("settings.html?welcome=true".into()))
+                    .title("Welcome to Blink - Let's Get Started!")
+                    .inner_size(750.0, 650.0)
+                    .center()
+            }
+            WindowKind::Conference => {
+                return Err(window_error(kind, "No call window is open".to_string()));
+            }
+        };
+
+        log::info!("Opening {} window", kind.label());
+        builder
+            .resizable(true)
+            .build()
+            .map_err(|e| window_error(kind, format!("Failed to create window: {}", e)))
+    }
+
+    /// focus_existing(app, kind)
+    /// What: Shows and focuses the window if it's open; false if it isn't
+    pub fn focus_existing<R: Runtime>(app: &AppHandle<R>, kind: WindowKind) -> bool {
+        match app.get_webview_window(kind.label()) {
+            Some(window) => {
+                focus(&window);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn focus<R: Runtime>(window: &WebviewWindow<R>) {
+    if let Err(e) = window.unminimize().and_then(|_| window.show()).and_then(|_| window.set_focus()) {
+        log::warn!("Failed to focus {} window: {}", window.label(), e);
+    }
+}
+
+fn window_error(kind: WindowKind, message: String) -> AppError {
+    AppError::Window { label: kind.label().to_string(), message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(WindowKind::Settings.label(), "settings");
+        assert_eq!(WindowKind::Welcome.label(), "welcome");
+        assert_eq!(WindowKind::Conference.label(), conference_window::WINDOW_LABEL);
+        assert_eq!(serde_json::from_str::<WindowKind>("\"settings\"").unwrap(), WindowKind::Settings);
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_settings_and_welcome_are_exclusive() {
+        assert!(WindowKind::Settings.exclusive_with().contains(&WindowKind::Welcome));
+        assert!(WindowKind::Welcome.exclusive_with().contains(&WindowKind::Settings));
+        assert!(WindowKind::Conference.exclusive_with().is_empty());
+    }
+}
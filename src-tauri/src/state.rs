@@ -7,6 +7,7 @@ use blink::storage::SettingsStore;
 use crate::services::global_shortcuts::GlobalShortcutService;
 use crate::services::conference_window::ConferenceWindow;
 use crate::controllers::call_controller::CallController;
+use crate::services::notifier::Notifier;
 use std::sync::Mutex;
 
 /// Application state
@@ -18,4 +19,6 @@ pub struct AppState {
     pub shortcuts: Mutex<GlobalShortcutService>,
     pub conference_window: Mutex<ConferenceWindow>,
     pub call_controller: Mutex<CallController>,
+    /// Immutable, so no lock (see notifier::notify)
+    pub notifier: Notifier,
 }
@@ -0,0 +1,312 @@
+// CLI argument parsing
+// What: Turns raw argv into a typed Command
+// Why: Scripts need a stable, predictable command grammar
+// Used by: cli::run()
+// Change notes: Hand-rolled on purpose - the grammar is small and we avoid extra deps
+
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+use blink::models::{KeySource, TargetType};
+
+/// Parsed command line
+/// What: Global flags plus the command to run
+/// Why: Global flags (--config, --json) apply to every command
+/// Used by: cli::run(), cli::commands::execute()
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cli {
+    /// Settings file override (defaults to SettingsStore::default_path())
+    pub config: Option<PathBuf>,
+    /// Emit machine-readable JSON instead of text
+    pub json: bool,
+    pub command: Command,
+}
+
+/// Every command the CLI understands
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    CodeNew,
+    Room { code: String },
+    TargetList,
+    TargetAdd {
+        label: String,
+        code: Option<String>,
+        target_type: TargetType,
+        primary: bool,
+        notes: Option<String>,
+    },
+    TargetRemove { id: String },
+    TargetSetPrimary { id: String },
+    /// Fold `remove` into `keep` (both must join the same room)
+    TargetMerge { keep: String, remove: String },
+    SettingsGet { path: Option<String> },
+    SettingsSet { path: String, value: String },
+    KeybindsShow,
+    /// Join a target's room (None = primary target), or one of its named channels
+    Join { target: Option<String>, channel: Option<String>, dry_run: bool },
+    EncryptionStatus,
+    /// Passphrase is filled from the environment by cli::run(), never argv
+    EncryptionEnable { method: KeySource, passphrase: Option<String> },
+    EncryptionDisable,
+}
+
+pub const USAGE: &str = "\
+Usage: blink [--config <path>] [--json] <command>
+
+Commands:
+  code new                          Generate a new pairing code
+  room <code>                       Show the room a code derives to
+  target list                       List configured targets
+  target add <label> [--code <code>] [--type person|group|link] [--primary] [--notes <text>]
+                                    Add a target (generates a code if none given;
+                                    for --type link, --code is the meeting URL)
+  target remove <id>                Remove a target
+  target set-primary <id>           Make a target the primary one
+  target merge <keep-id> <remove-id>
+                                    Merge two targets that join the same room
+  settings get [<path>]             Print settings, or one value by dotted path
+  settings set <path> <value>       Set an app_settings/keybinds value by dotted path
+                                    (parsed as the type of the current value)
+  keybinds show                     Show configured hotkeys
+  join [<id|label>] [--channel <name>] [--dry-run]
+                                    Open the meeting for a target (primary by default),
+                                    or a named channel of it; --dry-run only prints the URL
+  encryption status                 Show whether pairing codes are encrypted on disk
+  encryption enable keyfile|passphrase
+                                    Encrypt codes (passphrase read from BLINK_PASSPHRASE)
+  encryption disable                Store codes in plaintext again
+  help                              Show this message
+
+Global options:
+  --config <path>                   Use this settings file instead of the default
+  --json                            Print JSON output
+
+Environment:
+  BLINK_PASSPHRASE                  Passphrase for passphrase-encrypted settings";
+
+/// parse(args)
+/// What: Parses argv (without the program name) into a Cli
+/// Why: Keeps grammar errors separate from execution errors
+/// Used by: cli::run(), tests
+/// Contract:
+///   - Global flags may appear anywhere on the line
+///   - No command means Help
+///   - Unknown commands/flags are errors
+pub fn parse(args: &[String]) -> Result<Cli> {
+    let mut config = None;
+    let mut json = false;
+    let mut rest = Vec::new();
+    
+    // Pull global flags out first so they can appear anywhere
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" => {
+                let path = iter.next().context("--config needs a path")?;
+                config = Some(PathBuf::from(path));
+            }
+            "--json" => json = true,
+            "-h" | "--help" => rest.push("help".to_string()),
+            _ => rest.push(arg.clone()),
+        }
+    }
+    
+    let command = parse_command(&rest)?;
+    Ok(Cli { config, json, command })
+}
+
+fn parse_command(args: &[String]) -> Result<Command> {
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    
+    let command = match words.as_slice() {
+        [] | ["help", ..] => Command::Help,
+        ["code", "new"] => Command::CodeNew,
+        ["room", code] => Command::Room { code: code.to_string() },
+        ["target", "list"] => Command::TargetList,
+        ["target", "add", rest @ ..] => parse_target_add(rest)?,
+        ["target", "remove", id] => Command::TargetRemove { id: id.to_string() },
+        ["target", "set-primary", id] => Command::TargetSetPrimary { id: id.to_string() },
+        ["target", "merge", keep, remove] => Command::TargetMerge {
+            keep: keep.to_string(),
+            remove: remove.to_string(),
+        },
+        ["settings", "get"] => Command::SettingsGet { path: None },
+        ["settings", "get", path] => Command::SettingsGet { path: Some(path.to_string()) },
+        ["settings", "set", path, value] => Command::SettingsSet {
+            path: path.to_string(),
+            value: value.to_string(),
+        },
+        ["keybinds", "show"] => Command::KeybindsShow,
+        ["join", rest @ ..] => parse_join(rest)?,
+        ["encryption", "status"] => Command::EncryptionStatus,
+        ["encryption", "enable", "keyfile"] => Command::EncryptionEnable { method: KeySource::Keyfile, passphrase: None },
+        ["encryption", "enable", "passphrase"] => Command::EncryptionEnable { method: KeySource::Passphrase, passphrase: None },
+        ["encryption", "disable"] => Command::EncryptionDisable,
+        _ => bail!("Unrecognized command: {}", words.join(" ")),
+    };
+    
+    Ok(command)
+}
+
+fn parse_target_add(args: &[&str]) -> Result<Command> {
+    let mut label = None;
+    let mut code = None;
+    let mut target_type = TargetType::Person;
+    let mut primary = false;
+    let mut notes = None;
+    
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match *arg {
+            "--code" => code = Some(iter.next().context("--code needs a value")?.to_string()),
+            "--notes" => notes = Some(iter.next().context("--notes needs a value")?.to_string()),
+            "--type" => {
+                target_type = match *iter.next().context("--type needs a value")? {
+                    "person" => TargetType::Person,
+                    "group" => TargetType::Group,
+                    "link" => TargetType::Link,
+                    other => bail!("Unknown target type '{}' (expected person, group or link)", other),
+                };
+            }
+            "--primary" => primary = true,
+            flag if flag.starts_with("--") => bail!("Unknown option for target add: {}", flag),
+            value if label.is_none() => label = Some(value.to_string()),
+            value => bail!("Unexpected argument: {}", value),
+        }
+    }
+    
+    let label = label.context("target add needs a label")?;
+    Ok(Command::TargetAdd { label, code, target_type, primary, notes })
+}
+
+fn parse_join(args: &[&str]) -> Result<Command> {
+    let mut target = None;
+    let mut channel = None;
+    let mut dry_run = false;
+    
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match *arg {
+            "--dry-run" | "--print" => dry_run = true,
+            "--channel" => channel = Some(iter.next().context("--channel needs a value")?.to_string()),
+            flag if flag.starts_with("--") => bail!("Unknown option for join: {}", flag),
+            value if target.is_none() => target = Some(value.to_string()),
+            value => bail!("Unexpected argument: {}", value),
+        }
+    }
+    
+    Ok(Command::Join { target, channel, dry_run })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+    
+    #[test]
+    fn test_no_args_is_help() {
+        let cli = parse(&[]).unwrap();
+        assert_eq!(cli.command, Command::Help);
+        assert!(!cli.json);
+        assert!(cli.config.is_none());
+    }
+    
+    #[test]
+    fn test_global_flags_anywhere() {
+        let cli = parse(&args("target --json list --config /tmp/s.json")).unwrap();
+        assert_eq!(cli.command, Command::TargetList);
+        assert!(cli.json);
+        assert_eq!(cli.config, Some(PathBuf::from("/tmp/s.json")));
+    }
+    
+    #[test]
+    fn test_target_add_options() {
+        let cli = parse(&args("target add Team --type group --code abcd-efgh --primary --notes daily")).unwrap();
+        assert_eq!(cli.command, Command::TargetAdd {
+            label: "Team".to_string(),
+            code: Some("abcd-efgh".to_string()),
+            target_type: TargetType::Group,
+            primary: true,
+            notes: Some("daily".to_string()),
+        });
+        
+        // Defaults
+        let cli = parse(&args("target add Alice")).unwrap();
+        assert_eq!(cli.command, Command::TargetAdd {
+            label: "Alice".to_string(),
+            code: None,
+            target_type: TargetType::Person,
+            primary: false,
+            notes: None,
+        });
+    }
+    
+    #[test]
+    fn test_target_merge() {
+        assert_eq!(
+            parse(&args("target merge tg_a tg_b")).unwrap().command,
+            Command::TargetMerge { keep: "tg_a".to_string(), remove: "tg_b".to_string() }
+        );
+        assert!(parse(&args("target merge tg_a")).is_err());
+    }
+    
+    #[test]
+    fn test_settings_commands() {
+        assert_eq!(parse(&args("settings get")).unwrap().command, Command::SettingsGet { path: None });
+        assert_eq!(
+            parse(&args("settings set app_settings.autostart true")).unwrap().command,
+            Command::SettingsSet { path: "app_settings.autostart".to_string(), value: "true".to_string() }
+        );
+    }
+    
+    #[test]
+    fn test_join() {
+        assert_eq!(parse(&args("join")).unwrap().command, Command::Join { target: None, channel: None, dry_run: false });
+        assert_eq!(
+            parse(&args("join --dry-run Alice")).unwrap().command,
+            Command::Join { target: Some("Alice".to_string()), channel: None, dry_run: true }
+        );
+        assert_eq!(
+            parse(&args("join Alice --channel standup")).unwrap().command,
+            Command::Join { target: Some("Alice".to_string()), channel: Some("standup".to_string()), dry_run: false }
+        );
+        assert!(parse(&args("join --channel")).is_err());
+    }
+    
+    #[test]
+    fn test_encryption() {
+        assert_eq!(
+            parse(&args("encryption enable passphrase")).unwrap().command,
+            Command::EncryptionEnable { method: KeySource::Passphrase, passphrase: None }
+        );
+        assert_eq!(parse(&args("encryption disable")).unwrap().command, Command::EncryptionDisable);
+    }
+    
+    // Edge case tests
+    
+    #[test]
+    fn test_invalid_input() {
+        let cases = [
+            "bogus",
+            "code",
+            "room",
+            "target add",
+            "target add Alice --type robot",
+            "target add Alice Bob",
+            "target add Alice --frobnicate",
+            "settings set only-path",
+            "--config",
+            "join Alice Bob",
+            "join --now",
+            "encryption enable rot13",
+        ];
+        
+        for case in cases {
+            assert!(parse(&args(case)).is_err(), "Should fail for: {}", case);
+        }
+    }
+}
@@ -0,0 +1,612 @@
+// CLI command execution
+// What: Runs a parsed Command against the SettingsStore
+// Why: Lets scripts manage codes, targets and settings without the GUI
+// Used by: cli::run()
+// Calls: SettingsStore, core::{crypto, room}
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::process::Command as Process;
+
+use super::args::{Command, USAGE};
+use blink::core::{channel_room_id, generate_code_base32_100b, get_url_opener, meeting_url, room_id_from_code};
+use blink::models::settings::Keybinds;
+use blink::models::{BrowserSettings, CallDefaults, KeySource, NewTarget, Settings, Target, TargetType};
+use blink::storage::SettingsStore;
+
+/// Result of a command in both output formats
+/// What: JSON value for --json mode, text for humans
+/// Why: Every command supports both modes without duplicating logic
+/// Used by: cli::run() to print
+pub struct Output {
+    pub json: Value,
+    pub text: String,
+}
+
+impl Output {
+    fn new(json: Value, text: impl Into<String>) -> Self {
+        Self { json, text: text.into() }
+    }
+}
+
+/// execute(command, store)
+/// What: Runs one command, mutating and saving the store when needed
+/// Why: Single dispatch point keeps each command small
+/// Used by: cli::run(), tests
+pub fn execute(command: Command, store: &mut SettingsStore) -> Result<Output> {
+    match command {
+        Command::Help => Ok(Output::new(json!({ "usage": USAGE }), USAGE)),
+        
+        Command::CodeNew => {
+            let code = generate_code_base32_100b();
+            let room = room_id_from_code(&code);
+            Ok(Output::new(json!({ "code": code, "room": room }), code))
+        }
+        
+        Command::Room { code } => {
+            let room = room_id_from_code(&code);
+            Ok(Output::new(json!({ "code": code, "room": room }), room))
+        }
+        
+        Command::TargetList => {
+            let targets = store.get_targets();
+            let text = if targets.is_empty() {
+                "No targets configured".to_string()
+            } else {
+                targets.iter()
+                    .map(describe_target)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            Ok(Output::new(serde_json::to_value(targets)?, text))
+        }
+        
+        Command::TargetAdd { label, code, target_type, primary, notes } => {
+            let added = store.create_target(NewTarget {
+                label,
+                code: code.map(Into::into),
+                target_type,
+                is_primary: primary,
+                call_defaults: CallDefaults::default(),
+                notes,
+                channels: Vec::new(),
+            })?;
+            let text = format!("Added {}", describe_target(&added));
+            Ok(Output::new(serde_json::to_value(&added)?, text))
+        }
+        
+        Command::TargetRemove { id } => {
+            if !store.remove_target(&id)? {
+                bail!("No target with id '{}'", id);
+            }
+            Ok(Output::new(json!({ "removed": id }), format!("Removed {}", id)))
+        }
+        
+        Command::TargetSetPrimary { id } => {
+            if !store.set_primary_target(&id)? {
+                bail!("No target with id '{}'", id);
+            }
+            Ok(Output::new(json!({ "primary": id }), format!("Primary target is now {}", id)))
+        }
+        
+        Command::TargetMerge { keep, remove } => {
+            let merged = store.merge_targets(&keep, &remove)?;
+            let text = format!("Merged {} into {}", remove, describe_target(&merged));
+            Ok(Output::new(serde_json::to_value(&merged)?, text))
+        }
+        
+        Command::SettingsGet { path } => {
+            let root = serde_json::to_value(store.settings())?;
+            let value = match &path {
+                Some(path) => get_path(&root, path)
+                    .with_context(|| format!("No setting at '{}'", path))?
+                    .clone(),
+                None => root,
+            };
+            let text = match &value {
+                Value::String(s) => s.clone(),
+                other => serde_json::to_string_pretty(other)?,
+            };
+            Ok(Output::new(value, text))
+        }
+        
+        Command::SettingsSet { path, value } => {
+            check_settable(&path)?;
+            let root = serde_json::to_value(store.settings())?;
+            
+            // Round-trip through the schema so invalid edits never hit disk;
+            // the first candidate the schema accepts wins
+            let mut last_error = None;
+            let mut accepted = None;
+            for candidate in candidate_values(get_path(&root, &path), &value, &path)? {
+                let mut edited = root.clone();
+                set_path(&mut edited, &path, candidate.clone())?;
+                match serde_json::from_value::<Settings>(edited) {
+                    Ok(settings) => {
+                        accepted = Some((candidate, settings));
+                        break;
+                    }
+                    Err(e) => last_error = Some(e),
+                }
+            }
+            let Some((new_value, settings)) = accepted else {
+                let error = last_error.map(|e| e.to_string()).unwrap_or_default();
+                bail!("Invalid value for '{}': {}", path, error);
+            };
+            check_hotkey_owners(store, &settings.keybinds)?;
+            store.update_preferences(settings.app_settings, settings.keybinds)?;
+            
+            let text = format!("{} = {}", path, new_value);
+            Ok(Output::new(json!({ "path": path, "value": new_value }), text))
+        }
+        
+        Command::KeybindsShow => {
+            let keybinds = &store.settings().keybinds;
+            let mut lines = vec![
+                format!("join_primary: {}", keybinds.join_primary),
+                format!("hangup: {}", keybinds.hangup),
+            ];
+            if let Some(key) = &keybinds.toggle_mute {
+                lines.push(format!("toggle_mute: {}", key));
+            }
+            if let Some(key) = &keybinds.toggle_video {
+                lines.push(format!("toggle_video: {}", key));
+            }
+            if let Some(key) = &keybinds.toggle_mini_mode {
+                lines.push(format!("toggle_mini_mode: {}", key));
+            }
+            
+            // Sort for stable output (HashMap order is random)
+            let mut target_keys: Vec<_> = keybinds.target_hotkeys.iter().collect();
+            target_keys.sort();
+            for (id, key) in target_keys {
+                let label = store.get_target(id).map(|t| t.label.as_str()).unwrap_or("?");
+                lines.push(format!("{} ({}): {}", id, label, key));
+            }
+            let mut channel_keys: Vec<_> = keybinds.channel_hotkeys.iter().collect();
+            channel_keys.sort();
+            for (id, key) in channel_keys {
+                let label = store.find_channel(id)
+                    .map(|(target, channel)| format!("{} / {}", target.label, channel.name))
+                    .unwrap_or_else(|| "?".to_string());
+                lines.push(format!("{} ({}): {}", id, label, key));
+            }
+            
+            Ok(Output::new(serde_json::to_value(keybinds)?, lines.join("\n")))
+        }
+        
+        Command::EncryptionStatus => {
+            let method = store.settings().code_encryption.as_ref().map(|info| info.method);
+            let text = match method {
+                Some(KeySource::Keyfile) => "Pairing codes are encrypted (keyfile)",
+                Some(KeySource::Passphrase) => "Pairing codes are encrypted (passphrase)",
+                None => "Pairing codes are stored in plaintext",
+            };
+            Ok(Output::new(json!({ "encrypted": method.is_some(), "method": method }), text))
+        }
+        
+        Command::EncryptionEnable { method, passphrase } => {
+            store.enable_code_encryption(method, passphrase.as_deref())?;
+            Ok(Output::new(json!({ "encrypted": true, "method": method }), "Pairing codes encrypted"))
+        }
+        
+        Command::EncryptionDisable => {
+            store.disable_code_encryption()?;
+            Ok(Output::new(json!({ "encrypted": false }), "Pairing codes stored in plaintext"))
+        }
+        
+        Command::Join { target, channel, dry_run } => {
+            let target = match &target {
+                Some(query) => store.find_target(query)
+                    .with_context(|| format!("No target with id or label '{}'", query))?,
+                None => store.get_primary_target()
+                    .context("No primary target configured")?,
+            };
+            
+            // Any name works: the partner only needs the same code and channel name
+            let room = match (&channel, target.target_type) {
+                (Some(_), TargetType::Link) => bail!("'{}' is a meeting link and has no channels", target.label),
+                (None, TargetType::Link) => None,
+                (Some(name), _) => Some(channel_room_id(target.code.expose(), name)),
+                (None, _) => Some(room_id_from_code(target.code.expose())),
+            };
+            let url = room.as_deref().map(meeting_url).unwrap_or_else(|| target.meeting_url());
+            
+            if !dry_run {
+                open_url(&url, &store.settings().app_settings.browser)?;
+            }
+            
+            let text = if dry_run { url.clone() } else { format!("Joining {}: {}", target.label, url) };
+            Ok(Output::new(
+                json!({ "target": target.id, "channel": channel, "room": room, "url": url, "launched": !dry_run }),
+                text,
+            ))
+        }
+    }
+}
+
+/// Launch the configured browser, falling back to the OS URL opener
+/// (xdg-open on Linux) if there is none or it fails to start; never waits
+fn open_url(url: &str, browser: &BrowserSettings) -> Result<()> {
+    if let Some(command) = browser.launch_command(url) {
+        match Process::new(&command[0]).args(&command[1..]).spawn() {
+            Ok(_) => return Ok(()),
+            Err(e) => eprintln!("Warning: failed to launch {} ({}), using the default browser", command[0], e),
+        }
+    }
+    
+    let (program, args) = get_url_opener()
+        .split_first()
+        .context("No URL opener for this platform")?;
+    
+    Process::new(program)
+        .args(args)
+        .arg(url)
+        .spawn()
+        .with_context(|| format!("Failed to launch {}", program))?;
+    
+    Ok(())
+}
+
+/// One-line human description of a target
+fn describe_target(target: &Target) -> String {
+    format!(
+        "{}{}  {}  [{}]  {}",
+        if target.is_primary { "* " } else { "  " },
+        target.id,
+        target.label,
+        serde_json::to_value(target.target_type)
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_default(),
+        target.code.expose(),
+    )
+}
+
+/// Look up a dotted path ("app_settings.autostart", "targets.0.label")
+fn get_path<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(root, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+/// Only preferences can be set by path; targets and encryption have their own
+/// validated commands
+fn check_settable(path: &str) -> Result<()> {
+    match path.split('.').next().unwrap_or_default() {
+        "app_settings" | "keybinds" => Ok(()),
+        "targets" | "recent_calls" => {
+            bail!("'{}' can't be set directly; use `blink target ...` (see `blink help`)", path)
+        }
+        "code_encryption" => {
+            bail!("'{}' can't be set directly; use `blink encryption enable|disable`", path)
+        }
+        _ => bail!("Only app_settings.* and keybinds.* can be set, not '{}'", path),
+    }
+}
+
+/// New or changed per-target/per-channel hotkeys must name an existing id;
+/// otherwise they'd register a global hotkey that joins nothing
+fn check_hotkey_owners(store: &SettingsStore, keybinds: &Keybinds) -> Result<()> {
+    let current = &store.settings().keybinds;
+    for (id, hotkey) in &keybinds.target_hotkeys {
+        if current.target_hotkeys.get(id) != Some(hotkey) && store.get_target(id).is_none() {
+            bail!("No target with id '{}'", id);
+        }
+    }
+    for (id, hotkey) in &keybinds.channel_hotkeys {
+        if current.channel_hotkeys.get(id) != Some(hotkey) && store.find_channel(id).is_none() {
+            bail!("No channel with id '{}'", id);
+        }
+    }
+    Ok(())
+}
+
+/// candidate_values(existing, text, path)
+/// What: How to read `text` for a setting whose current value is `existing`
+/// Contract:
+///   - Strings stay strings ("2024", "true" included); booleans, numbers,
+///     objects and lists must parse as that JSON type
+///   - Unset/null (optional fields, new map keys): a plain string first,
+///     then the JSON literal, for the schema to decide
+fn candidate_values(existing: Option<&Value>, text: &str, path: &str) -> Result<Vec<Value>> {
+    let json = serde_json::from_str::<Value>(text).ok();
+    let expected = match existing {
+        Some(Value::String(_)) => return Ok(vec![Value::String(text.to_string())]),
+        Some(Value::Bool(_)) => "true or false",
+        Some(Value::Number(_)) => "a number",
+        Some(Value::Object(_)) => "a JSON object",
+        Some(Value::Array(_)) => "a JSON list",
+        Some(Value::Null) | None => {
+            let mut candidates = vec![Value::String(text.to_string())];
+            candidates.extend(json.filter(|v| !v.is_string()));
+            return Ok(candidates);
+        }
+    };
+    let same_type = matches!(
+        (existing, &json),
+        (Some(Value::Bool(_)), Some(Value::Bool(_)))
+            | (Some(Value::Number(_)), Some(Value::Number(_)))
+            | (Some(Value::Object(_)), Some(Value::Object(_)))
+            | (Some(Value::Array(_)), Some(Value::Array(_)))
+    );
+    match json {
+        Some(value) if same_type => Ok(vec![value]),
+        _ => bail!("'{}' expects {}, got '{}'", path, expected, text),
+    }
+}
+
+/// Set a dotted path; the parent must exist, the last key may be new (object maps)
+fn set_path(root: &mut Value, path: &str, new_value: Value) -> Result<()> {
+    let (parent_path, last) = match path.rsplit_once('.') {
+        Some((parent, last)) => (Some(parent), last),
+        None => (None, path),
+    };
+    
+    let mut parent = root;
+    if let Some(parent_path) = parent_path {
+        for key in parent_path.split('.') {
+            parent = match parent {
+                Value::Object(map) => map.get_mut(key),
+                Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get_mut(i)),
+                _ => None,
+            }
+            .with_context(|| format!("No setting at '{}'", parent_path))?;
+        }
+    }
+    
+    match parent {
+        Value::Object(map) => {
+            map.insert(last.to_string(), new_value);
+        }
+        Value::Array(items) => {
+            let slot = last.parse::<usize>().ok()
+                .and_then(|i| items.get_mut(i))
+                .with_context(|| format!("No setting at '{}'", path))?;
+            *slot = new_value;
+        }
+        _ => bail!("'{}' is not an object or list", parent_path.unwrap_or("")),
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn temp_store() -> (TempDir, SettingsStore) {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        (temp_dir, store)
+    }
+    
+    fn add(store: &mut SettingsStore, label: &str, primary: bool) -> String {
+        let command = Command::TargetAdd {
+            label: label.to_string(),
+            code: None,
+            target_type: TargetType::Person,
+            primary,
+            notes: None,
+        };
+        let output = execute(command, store).unwrap();
+        output.json["id"].as_str().unwrap().to_string()
+    }
+    
+    #[test]
+    fn test_code_new_and_room() {
+        let (_dir, mut store) = temp_store();
+        
+        let output = execute(Command::CodeNew, &mut store).unwrap();
+        let code = output.json["code"].as_str().unwrap().to_string();
+        assert_eq!(code.len(), 24);
+        
+        let output = execute(Command::Room { code: code.clone() }, &mut store).unwrap();
+        assert_eq!(output.text, room_id_from_code(&code));
+    }
+    
+    #[test]
+    fn test_target_lifecycle() {
+        let (dir, mut store) = temp_store();
+        
+        let first = add(&mut store, "Alice", false);
+        let second = add(&mut store, "Bob", true);
+        assert!(first.starts_with("tg_"));
+        assert_ne!(first, second);
+        
+        // --primary moves primary away from the auto-primary first target
+        assert_eq!(store.get_primary_target().unwrap().id, second);
+        
+        execute(Command::TargetSetPrimary { id: first.clone() }, &mut store).unwrap();
+        assert_eq!(store.get_primary_target().unwrap().id, first);
+        
+        execute(Command::TargetRemove { id: second.clone() }, &mut store).unwrap();
+        assert!(execute(Command::TargetRemove { id: second }, &mut store).is_err());
+        
+        // Everything was persisted
+        let loaded = SettingsStore::load_from_path(dir.path().join("settings.json")).unwrap();
+        assert_eq!(loaded.get_targets().len(), 1);
+        
+        let output = execute(Command::TargetList, &mut store).unwrap();
+        assert_eq!(output.json.as_array().unwrap().len(), 1);
+        assert!(output.text.contains("Alice"));
+    }
+    
+    #[test]
+    fn test_settings_get_and_set() {
+        let (_dir, mut store) = temp_store();
+        
+        let output = execute(Command::SettingsGet { path: Some("app_settings.autostart".into()) }, &mut store).unwrap();
+        assert_eq!(output.json, json!(false));
+        
+        execute(Command::SettingsSet { path: "app_settings.autostart".into(), value: "true".into() }, &mut store).unwrap();
+        assert!(store.settings().app_settings.autostart);
+        
+        // Plain strings don't need JSON quoting
+        execute(Command::SettingsSet { path: "keybinds.hangup".into(), value: "Ctrl+Alt+H".into() }, &mut store).unwrap();
+        assert_eq!(store.settings().keybinds.hangup, "Ctrl+Alt+H");
+        
+        // New map keys are allowed for existing targets
+        let id = add(&mut store, "Alice", false);
+        execute(Command::SettingsSet { path: format!("keybinds.target_hotkeys.{}", id), value: "Ctrl+1".into() }, &mut store).unwrap();
+        assert_eq!(store.settings().keybinds.target_hotkeys[&id], "Ctrl+1");
+    }
+    
+    #[test]
+    fn test_keybinds_show() {
+        let (_dir, mut store) = temp_store();
+        let output = execute(Command::KeybindsShow, &mut store).unwrap();
+        assert!(output.text.contains("join_primary"));
+        assert_eq!(output.json["hangup"], json!(store.settings().keybinds.hangup));
+    }
+    
+    #[test]
+    fn test_join_dry_run() {
+        let (_dir, mut store) = temp_store();
+        let alice = add(&mut store, "Alice", false);
+        add(&mut store, "Bob", false);
+        
+        let code = store.get_target(&alice).unwrap().code.expose().clone();
+        let expected = meeting_url(&room_id_from_code(&code));
+        
+        // Primary by default (first target)
+        let output = execute(Command::Join { target: None, channel: None, dry_run: true }, &mut store).unwrap();
+        assert_eq!(output.text, expected);
+        assert_eq!(output.json["launched"], json!(false));
+        
+        // By label, case-insensitive
+        let output = execute(Command::Join { target: Some("bob".into()), channel: None, dry_run: true }, &mut store).unwrap();
+        assert_ne!(output.text, expected);
+        assert!(output.text.starts_with("https://meet.jit.si/JustCallRoom"));
+        
+        // A channel of the primary target
+        let command = Command::Join { target: None, channel: Some("Standup".into()), dry_run: true };
+        let output = execute(command, &mut store).unwrap();
+        assert_eq!(output.text, meeting_url(&channel_room_id(&code, "standup")));
+        assert_eq!(output.json["channel"], json!("Standup"));
+    }
+    
+    #[test]
+    fn test_join_link_target() {
+        let (_dir, mut store) = temp_store();
+        let command = Command::TargetAdd {
+            label: "Weekly".to_string(),
+            code: Some("zoom.us/j/123".to_string()),
+            target_type: TargetType::Link,
+            primary: true,
+            notes: None,
+        };
+        execute(command, &mut store).unwrap();
+        
+        let output = execute(Command::Join { target: None, channel: None, dry_run: true }, &mut store).unwrap();
+        assert_eq!(output.text, "https://zoom.us/j/123");
+        assert_eq!(output.json["room"], Value::Null);
+        
+        let command = Command::Join { target: None, channel: Some("standup".into()), dry_run: true };
+        assert!(execute(command, &mut store).is_err());
+    }
+    
+    #[test]
+    fn test_encryption_commands() {
+        let (dir, mut store) = temp_store();
+        add(&mut store, "Alice", false);
+        
+        let output = execute(Command::EncryptionStatus, &mut store).unwrap();
+        assert_eq!(output.json["encrypted"], json!(false));
+        
+        execute(Command::EncryptionEnable { method: KeySource::Keyfile, passphrase: None }, &mut store).unwrap();
+        let output = execute(Command::EncryptionStatus, &mut store).unwrap();
+        assert_eq!(output.json["method"], json!("keyfile"));
+        assert!(dir.path().join("settings.key").exists());
+        
+        // Passphrase mode needs the passphrase
+        let result = execute(Command::EncryptionEnable { method: KeySource::Passphrase, passphrase: None }, &mut store);
+        assert!(result.is_err());
+        
+        execute(Command::EncryptionDisable, &mut store).unwrap();
+        assert!(store.settings().code_encryption.is_none());
+    }
+    
+    // Edge case tests
+    
+    #[test]
+    fn test_join_without_targets() {
+        let (_dir, mut store) = temp_store();
+        assert!(execute(Command::Join { target: None, channel: None, dry_run: true }, &mut store).is_err());
+        assert!(execute(Command::Join { target: Some("x".into()), channel: None, dry_run: true }, &mut store).is_err());
+    }
+    
+    #[test]
+    fn test_settings_set_rejects_invalid() {
+        let (_dir, mut store) = temp_store();
+        
+        // Wrong type for the schema
+        let result = execute(Command::SettingsSet { path: "app_settings.autostart".into(), value: "maybe".into() }, &mut store);
+        assert!(result.is_err());
+        assert!(!store.settings().app_settings.autostart);
+        
+        // Missing parent
+        let result = execute(Command::SettingsSet { path: "nope.deeper".into(), value: "1".into() }, &mut store);
+        assert!(result.is_err());
+        
+        // Unknown path on get
+        assert!(execute(Command::SettingsGet { path: Some("nope".into()) }, &mut store).is_err());
+        
+        // Numbers must be numbers
+        let result = execute(Command::SettingsSet { path: "app_settings.auto_hangup_after_secs".into(), value: "soon".into() }, &mut store);
+        assert!(result.is_err());
+    }
+    
+    #[test]
+    fn test_settings_set_refuses_targets_and_encryption() {
+        let (_dir, mut store) = temp_store();
+        let id = add(&mut store, "Alice", false);
+        let code = store.get_target(&id).unwrap().code.expose().clone();
+        
+        // `target add --code abc` is rejected as too weak; so is this back door
+        let result = execute(Command::SettingsSet { path: "targets.0.code".into(), value: "abc".into() }, &mut store);
+        assert!(result.err().unwrap().to_string().contains("blink target"));
+        assert_eq!(store.get_target(&id).unwrap().code.expose(), &code);
+        
+        for path in ["code_encryption", "recent_calls", "version"] {
+            let result = execute(Command::SettingsSet { path: path.into(), value: "null".into() }, &mut store);
+            assert!(result.is_err(), "{} was settable", path);
+        }
+    }
+    
+    #[test]
+    fn test_settings_set_rejects_unknown_hotkey_owners() {
+        let (_dir, mut store) = temp_store();
+        add(&mut store, "Alice", false);
+        
+        let result = execute(Command::SettingsSet { path: "keybinds.target_hotkeys.tg_x".into(), value: "Ctrl+1".into() }, &mut store);
+        assert!(result.err().unwrap().to_string().contains("No target with id 'tg_x'"));
+        let result = execute(Command::SettingsSet { path: "keybinds.channel_hotkeys.ch_x".into(), value: "Ctrl+2".into() }, &mut store);
+        assert!(result.err().unwrap().to_string().contains("No channel with id 'ch_x'"));
+        let result = execute(Command::SettingsSet { path: "keybinds.target_hotkeys".into(), value: r#"{"tg_y": "Ctrl+3"}"#.into() }, &mut store);
+        assert!(result.is_err());
+        
+        let keybinds = &store.settings().keybinds;
+        assert!(keybinds.target_hotkeys.is_empty() && keybinds.channel_hotkeys.is_empty());
+    }
+    
+    #[test]
+    fn test_settings_set_keeps_string_values() {
+        let (_dir, mut store) = temp_store();
+        
+        // Text that looks like JSON stays a string for string fields
+        execute(Command::SettingsSet { path: "keybinds.hangup".into(), value: "2024".into() }, &mut store).unwrap();
+        assert_eq!(store.settings().keybinds.hangup, "2024");
+        
+        // Unset optional string
+        execute(Command::SettingsSet { path: "app_settings.browser.executable".into(), value: "true".into() }, &mut store).unwrap();
+        assert_eq!(store.settings().app_settings.browser.executable.as_deref(), Some("true"));
+        
+        // Numbers parse as numbers
+        execute(Command::SettingsSet { path: "app_settings.auto_hangup_after_secs".into(), value: "600".into() }, &mut store).unwrap();
+        assert_eq!(store.settings().app_settings.auto_hangup_after_secs, 600);
+    }
+}
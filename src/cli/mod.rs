@@ -0,0 +1,79 @@
+// Command-line interface
+// What: `blink` subcommands for codes, rooms, targets, settings and keybinds
+// Why: Lets users script the same SettingsStore file the app uses
+// Used by: main.rs
+// Change notes: New commands go in args::Command + commands::execute()
+
+pub mod args;
+pub mod commands;
+
+use args::Command;
+use blink::storage::{SettingsLocked, SettingsStore};
+
+/// Environment variable holding the settings passphrase (kept out of argv/history)
+pub const PASSPHRASE_ENV: &str = "BLINK_PASSPHRASE";
+
+/// run(args)
+/// What: Parses argv, runs the command, prints output
+/// Why: Keeps main() trivial and gives a single exit-code policy
+/// Used by: main()
+/// Contract:
+///   - Returns 0 on success, 1 on command failure, 2 on usage errors
+///   - Errors go to stderr (as {"error": ...} in --json mode)
+pub fn run(raw_args: &[String]) -> i32 {
+    let cli = match args::parse(raw_args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, args::USAGE);
+            return 2;
+        }
+    };
+    
+    let passphrase = std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty());
+    
+    let result = (|| {
+        let path = match &cli.config {
+            Some(path) => path.clone(),
+            None => SettingsStore::default_path()?,
+        };
+        let mut store = match &passphrase {
+            Some(passphrase) => SettingsStore::load_from_path_with_passphrase(path, passphrase),
+            None => SettingsStore::load_from_path(path),
+        }
+        .map_err(|e| match e.downcast_ref::<SettingsLocked>() {
+            Some(_) => anyhow::anyhow!("{}; set {} to unlock", e, PASSPHRASE_ENV),
+            None => e,
+        })?;
+        for issue in store.security_warnings() {
+            eprintln!("Warning: {}", issue);
+        }
+        for warning in store.data_warnings() {
+            eprintln!("Warning: {}", warning);
+        }
+        
+        let command = match cli.command.clone() {
+            Command::EncryptionEnable { method, .. } => Command::EncryptionEnable { method, passphrase: passphrase.clone() },
+            other => other,
+        };
+        commands::execute(command, &mut store)
+    })();
+    
+    match result {
+        Ok(output) => {
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&output.json).unwrap_or_default());
+            } else {
+                println!("{}", output.text);
+            }
+            0
+        }
+        Err(e) => {
+            if cli.json {
+                eprintln!("{}", serde_json::json!({ "error": format!("{:#}", e) }));
+            } else {
+                eprintln!("Error: {:#}", e);
+            }
+            1
+        }
+    }
+}
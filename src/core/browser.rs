@@ -0,0 +1,151 @@
+// Browser command line builder
+// What: Builds the argv that opens a meeting URL in a chosen browser/profile
+// Why: The system default browser opens a tab lost among all the others; a
+//      specific browser, profile and Chromium's --app=URL window fix that
+// Used by:
+//   - BrowserSettings::launch_command() (models)
+//   - CLI `blink join`, Tauri ExternalBrowserService
+// Change notes: Callers fall back to get_url_opener() if the launch fails
+
+/// How a browser takes profile/app-window arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserFamily {
+    /// Chrome, Chromium, Edge, Brave, Vivaldi, Opera
+    Chromium,
+    /// Firefox and forks (no app-window mode)
+    Firefox,
+    /// Unknown: just `<executable> <url>`
+    Other,
+}
+
+/// browser_family(executable)
+/// What: Guesses the family from the executable or .app bundle name
+pub fn browser_family(executable: &str) -> BrowserFamily {
+    let name = executable
+        .trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(executable)
+        .to_lowercase();
+
+    if ["chrom", "brave", "edge", "vivaldi", "opera"].iter().any(|n| name.contains(n)) {
+        BrowserFamily::Chromium
+    } else if ["firefox", "librewolf", "waterfox"].iter().any(|n| name.contains(n)) {
+        BrowserFamily::Firefox
+    } else {
+        BrowserFamily::Other
+    }
+}
+
+/// browser_command(executable, profile, app_mode, url)
+/// What: Full argv (program first) to open url in the given browser
+/// Contract:
+///   - profile: a name ("Profile 1", "work") or a directory (contains a path
+///     separator); Chromium gets --profile-directory / --user-data-dir,
+///     Firefox -P / --profile; ignored for unknown browsers
+///   - app_mode: Chromium --app=URL window; Firefox has no equivalent and
+///     gets --new-window; ignored for unknown browsers
+///   - A macOS .app bundle is launched through `open -na <bundle> --args ...`
+///   - url must be an http(s) URL (meeting_url() or a validated Link target)
+pub fn browser_command(executable: &str, profile: Option<&str>, app_mode: bool, url: &str) -> Vec<String> {
+    let profile = profile.map(str::trim).filter(|p| !p.is_empty());
+    let is_path = |p: &str| p.contains(['/', '\\']);
+
+    let mut args = Vec::new();
+    match browser_family(executable) {
+        BrowserFamily::Chromium => {
+            match profile {
+                Some(dir) if is_path(dir) => args.push(format!("--user-data-dir={}", dir)),
+                Some(name) => args.push(format!("--profile-directory={}", name)),
+                None => {}
+            }
+            if app_mode {
+                args.push(format!("--app={}", url));
+            } else {
+                args.push(url.to_string());
+            }
+        }
+        BrowserFamily::Firefox => {
+            match profile {
+                Some(dir) if is_path(dir) => args.extend(["--profile".to_string(), dir.to_string()]),
+                Some(name) => args.extend(["-P".to_string(), name.to_string()]),
+                None => {}
+            }
+            if app_mode {
+                args.push("--new-window".to_string());
+            }
+            args.push(url.to_string());
+        }
+        BrowserFamily::Other => args.push(url.to_string()),
+    }
+
+    let executable = executable.trim_end_matches(['/', '\\']);
+    if executable.to_lowercase().ends_with(".app") {
+        let mut command = vec!["open".to_string(), "-na".to_string(), executable.to_string(), "--args".to_string()];
+        command.extend(args);
+        command
+    } else {
+        let mut command = vec![executable.to_string()];
+        command.extend(args);
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://meet.jit.si/JustCallRoomabcd2345";
+
+    #[test]
+    fn test_browser_family() {
+        assert_eq!(browser_family("google-chrome-stable"), BrowserFamily::Chromium);
+        assert_eq!(browser_family("/usr/bin/chromium-browser"), BrowserFamily::Chromium);
+        assert_eq!(browser_family(r"C:\Program Files\Microsoft\Edge\Application\msedge.exe"), BrowserFamily::Chromium);
+        assert_eq!(browser_family("/Applications/Brave Browser.app"), BrowserFamily::Chromium);
+        assert_eq!(browser_family("firefox"), BrowserFamily::Firefox);
+        assert_eq!(browser_family("/usr/bin/epiphany"), BrowserFamily::Other);
+    }
+
+    #[test]
+    fn test_chromium_command() {
+        assert_eq!(browser_command("chromium", None, false, URL), ["chromium", URL]);
+        assert_eq!(
+            browser_command("google-chrome", Some("Profile 1"), true, URL),
+            ["google-chrome", "--profile-directory=Profile 1", &format!("--app={}", URL)]
+        );
+        assert_eq!(
+            browser_command("chromium", Some("/home/me/.config/calls"), false, URL),
+            ["chromium", "--user-data-dir=/home/me/.config/calls", URL]
+        );
+    }
+
+    #[test]
+    fn test_firefox_command() {
+        assert_eq!(browser_command("firefox", Some("work"), false, URL), ["firefox", "-P", "work", URL]);
+        assert_eq!(
+            browser_command("firefox", Some("/tmp/ff-profile"), true, URL),
+            ["firefox", "--profile", "/tmp/ff-profile", "--new-window", URL]
+        );
+    }
+
+    #[test]
+    fn test_macos_bundle() {
+        assert_eq!(
+            browser_command("/Applications/Google Chrome.app/", None, true, URL),
+            ["open", "-na", "/Applications/Google Chrome.app", "--args", &format!("--app={}", URL)]
+        );
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_unknown_browser_ignores_options() {
+        assert_eq!(browser_command("/usr/bin/epiphany", Some("work"), true, URL), ["/usr/bin/epiphany", URL]);
+    }
+
+    #[test]
+    fn test_blank_profile_ignored() {
+        assert_eq!(browser_command("chromium", Some("  "), false, URL), ["chromium", URL]);
+    }
+}
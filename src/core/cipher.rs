@@ -0,0 +1,245 @@
+// Authenticated encryption for data at rest
+// What: Seal/open byte blobs with a passphrase-derived or random key
+// Why: Bundles and settings.json carry pairing codes; a copied file must not leak rooms
+// Used by:
+//   - storage::bundle (passphrase-protected export/import)
+//   - storage::code_encryption (pairing codes in settings.json)
+// Change notes: SealedBox is serialized into files - only add fields with defaults
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use data_encoding::BASE64;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Key derivation identifier stored alongside the ciphertext
+pub const KDF_ARGON2ID: &str = "argon2id";
+
+/// Length of a raw symmetric key in bytes
+pub const KEY_LEN: usize = 32;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Encrypted payload plus everything needed to decrypt it (except the secret)
+/// What: Base64 salt/nonce/ciphertext with the KDF name
+/// Why: Self-describing so files stay readable if we change KDF later
+/// Used by: storage::bundle::BundleFile::Encrypted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SealedBox {
+    pub kdf: String,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// seal_with_passphrase(plaintext, passphrase)
+/// What: Encrypts with XChaCha20-Poly1305 under an Argon2id-derived key
+/// Why: Users protect exported bundles with something they can remember
+/// Used by: storage::bundle export
+/// Contract:
+///   - Fresh random salt and nonce every call
+///   - Empty passphrase is rejected
+pub fn seal_with_passphrase(plaintext: &[u8], passphrase: &str) -> Result<SealedBox> {
+    if passphrase.is_empty() {
+        bail!("Passphrase must not be empty");
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+    let key = derive_key(passphrase, &salt)?;
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow!("Encryption failed"))?;
+
+    Ok(SealedBox {
+        kdf: KDF_ARGON2ID.to_string(),
+        salt: BASE64.encode(&salt),
+        nonce: BASE64.encode(&nonce),
+        ciphertext: BASE64.encode(&ciphertext),
+    })
+}
+
+/// open_with_passphrase(sealed, passphrase)
+/// What: Decrypts a SealedBox produced by seal_with_passphrase()
+/// Why: Import path for passphrase-protected bundles
+/// Used by: storage::bundle import
+/// Contract:
+///   - Wrong passphrase and tampered data both fail authentication
+pub fn open_with_passphrase(sealed: &SealedBox, passphrase: &str) -> Result<Vec<u8>> {
+    if sealed.kdf != KDF_ARGON2ID {
+        bail!("Unsupported key derivation '{}'", sealed.kdf);
+    }
+
+    let salt = decode_field(&sealed.salt, "salt")?;
+    let nonce = decode_field(&sealed.nonce, "nonce")?;
+    let ciphertext = decode_field(&sealed.ciphertext, "ciphertext")?;
+    if nonce.len() != NONCE_LEN {
+        bail!("Invalid nonce length {}", nonce.len());
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+    XChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Decryption failed: wrong passphrase or corrupted data"))
+}
+
+/// generate_key()
+/// What: Fresh random key from the OS CSPRNG
+/// Used by: storage::code_encryption keyfile creation
+pub fn generate_key() -> [u8; KEY_LEN] {
+    let mut key = [0u8; KEY_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    key
+}
+
+/// generate_salt()
+/// What: Fresh random salt for derive_key()
+/// Used by: storage::code_encryption passphrase mode
+pub fn generate_salt() -> Vec<u8> {
+    let mut salt = vec![0u8; SALT_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// derive_key(passphrase, salt)
+/// What: Argon2id with the crate's recommended defaults (19 MiB, 2 passes)
+/// Why: Slow on purpose so stolen files resist passphrase guessing
+/// Used by: passphrase sealing, storage::code_encryption
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// seal_with_key(key, plaintext)
+/// What: Encrypts under a raw key; output is base64(nonce || ciphertext)
+/// Why: Compact enough to store inline in a JSON string field
+/// Used by: storage::code_encryption
+pub fn seal_with_key(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow!("Encryption failed"))?;
+
+    let mut combined = nonce.to_vec();
+    combined.extend_from_slice(&ciphertext);
+    Ok(BASE64.encode(&combined))
+}
+
+/// open_with_key(key, sealed)
+/// What: Reverses seal_with_key()
+/// Used by: storage::code_encryption
+pub fn open_with_key(key: &[u8; KEY_LEN], sealed: &str) -> Result<Vec<u8>> {
+    let combined = decode_field(sealed, "sealed value")?;
+    if combined.len() < NONCE_LEN {
+        bail!("Sealed value too short");
+    }
+
+    let (nonce, ciphertext) = combined.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Decryption failed: wrong key or corrupted data"))
+}
+
+fn decode_field(value: &str, name: &str) -> Result<Vec<u8>> {
+    BASE64.decode(value.as_bytes())
+        .with_context(|| format!("Invalid base64 in {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let sealed = seal_with_passphrase(b"secret codes", "correct horse").unwrap();
+        assert_eq!(sealed.kdf, KDF_ARGON2ID);
+
+        let opened = open_with_passphrase(&sealed, "correct horse").unwrap();
+        assert_eq!(opened, b"secret codes");
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails() {
+        let sealed = seal_with_passphrase(b"secret codes", "correct horse").unwrap();
+        let result = open_with_passphrase(&sealed, "battery staple");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("wrong passphrase"));
+    }
+
+    #[test]
+    fn test_fresh_salt_and_nonce() {
+        let a = seal_with_passphrase(b"same", "pass").unwrap();
+        let b = seal_with_passphrase(b"same", "pass").unwrap();
+        assert_ne!(a.salt, b.salt);
+        assert_ne!(a.nonce, b.nonce);
+        assert_ne!(a.ciphertext, b.ciphertext);
+    }
+
+    #[test]
+    fn test_key_round_trip() {
+        let key = generate_key();
+        let sealed = seal_with_key(&key, b"abcd-efgh").unwrap();
+        assert_eq!(open_with_key(&key, &sealed).unwrap(), b"abcd-efgh");
+
+        // Same key, different nonce each time
+        assert_ne!(sealed, seal_with_key(&key, b"abcd-efgh").unwrap());
+
+        assert!(open_with_key(&generate_key(), &sealed).is_err());
+    }
+
+    #[test]
+    fn test_derive_key_deterministic() {
+        let salt = generate_salt();
+        assert_eq!(derive_key("pass", &salt).unwrap(), derive_key("pass", &salt).unwrap());
+        assert_ne!(derive_key("pass", &salt).unwrap(), derive_key("other", &salt).unwrap());
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_open_with_key_malformed() {
+        let key = generate_key();
+        assert!(open_with_key(&key, "").is_err());
+        assert!(open_with_key(&key, "not base64!").is_err());
+        assert!(open_with_key(&key, &BASE64.encode(&[0u8; 10])).is_err());
+    }
+
+    #[test]
+    fn test_tampering_detected() {
+        let mut sealed = seal_with_passphrase(b"secret codes", "pass").unwrap();
+        let mut bytes = BASE64.decode(sealed.ciphertext.as_bytes()).unwrap();
+        bytes[0] ^= 0x01;
+        sealed.ciphertext = BASE64.encode(&bytes);
+        assert!(open_with_passphrase(&sealed, "pass").is_err());
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert!(seal_with_passphrase(b"data", "").is_err());
+
+        let sealed = seal_with_passphrase(b"data", "pass").unwrap();
+
+        let mut bad_kdf = sealed.clone();
+        bad_kdf.kdf = "rot13".to_string();
+        assert!(open_with_passphrase(&bad_kdf, "pass").is_err());
+
+        let mut bad_nonce = sealed.clone();
+        bad_nonce.nonce = BASE64.encode(&[0u8; 5]);
+        assert!(open_with_passphrase(&bad_nonce, "pass").is_err());
+
+        let mut bad_base64 = sealed;
+        bad_base64.salt = "!!!".to_string();
+        assert!(open_with_passphrase(&bad_base64, "pass").is_err());
+    }
+}
@@ -8,6 +8,7 @@
 
 use rand::RngCore;
 use data_encoding::BASE32_NOPAD;
+use serde::{Deserialize, Serialize};
 
 /// generate_code_base32_100b()
 /// What: Creates a cryptographically secure 100-bit code formatted for humans
@@ -53,6 +54,277 @@ pub fn generate_code_base32_100b() -> String {
     )
 }
 
+/// Number of random base32 chars in a generated id (80 bits)
+pub const ID_RANDOM_CHARS: usize = 16;
+
+/// generate_id(prefix)
+/// What: Creates an identifier like "tg_k3m2p7xq4rbd9fwa" from the OS CSPRNG
+/// Why: Ids were invented by callers ("tg_demo") and could collide across
+///      machines when bundles are imported
+/// Used by: SettingsStore::create_target()
+/// Contract:
+///   - Returns: "{prefix}_" + 16 lowercase base32 chars (80 bits)
+///   - Safe to generate independently on different machines
+pub fn generate_id(prefix: &str) -> String {
+    let mut raw_bytes = [0u8; 10];
+    rand::rngs::OsRng.fill_bytes(&mut raw_bytes);
+    
+    // 10 bytes = 80 bits = exactly 16 base32 chars, no padding
+    let encoded = BASE32_NOPAD.encode(&raw_bytes).to_lowercase();
+    format!("{}_{}", prefix, encoded)
+}
+
+/// How guessable a pairing code is
+/// What: Coarse class of estimate_code_strength()'s bit estimate
+/// Why: Anyone who guesses a code can join that room on the public server
+/// Used by: AppSettings.min_code_strength, SettingsStore target validation, settings UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeStrength {
+    /// Guessable by a dictionary/pattern attack (e.g. "a", "password123")
+    Weak,
+    /// Memorable but not trivially guessable (e.g. three words)
+    #[default]
+    Ok,
+    /// Comparable to a generated code
+    Strong,
+}
+
+impl CodeStrength {
+    /// Minimum estimated bits for this class
+    pub fn min_bits(self) -> f64 {
+        match self {
+            CodeStrength::Weak => 0.0,
+            CodeStrength::Ok => OK_CODE_BITS,
+            CodeStrength::Strong => STRONG_CODE_BITS,
+        }
+    }
+}
+
+impl std::fmt::Display for CodeStrength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CodeStrength::Weak => "weak",
+            CodeStrength::Ok => "ok",
+            CodeStrength::Strong => "strong",
+        })
+    }
+}
+
+/// Estimated bits needed for CodeStrength::Ok (~7e10 guesses)
+pub const OK_CODE_BITS: f64 = 36.0;
+/// Estimated bits needed for CodeStrength::Strong
+pub const STRONG_CODE_BITS: f64 = 64.0;
+
+/// Result of estimate_code_strength()
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CodeStrengthEstimate {
+    /// log2 of the guesses an attacker with a wordlist needs (rounded down to 0.1)
+    pub bits: f64,
+    pub strength: CodeStrength,
+}
+
+/// Bits charged for a word an attacker would find in a wordlist
+/// (log2 of a ~8000-word list, like diceware)
+const WORD_BITS: f64 = 13.0;
+
+/// Words and names people pick for memorable codes, plus common passwords
+/// Lowercase, 3+ chars. Not exhaustive: any listed word is charged WORD_BITS
+/// instead of per-letter bits, which is what a real wordlist attack costs.
+const COMMON_WORDS: &[&str] = &[
+    // Passwords and filler
+    "password", "passwd", "pass", "secret", "letmein", "welcome", "admin", "login", "qwerty",
+    "dragon", "monkey", "master", "shadow", "sunshine", "princess", "football", "baseball",
+    "iloveyou", "love", "hello", "test", "demo", "default", "guest", "user", "root", "abc",
+    "code", "room", "call", "meet", "meeting", "chat", "video", "talk", "join", "blink",
+    "jitsi", "zoom", "team", "teams", "group", "daily", "weekly", "standup", "sync", "huddle",
+    "office", "work", "home", "family", "friends", "friend", "private", "public", "open",
+    // Names
+    "alice", "bob", "carol", "dave", "david", "eve", "frank", "grace", "heidi", "john", "jane",
+    "mike", "michael", "sarah", "chris", "alex", "sam", "anna", "emma", "olivia", "noah",
+    "liam", "james", "mary", "linda", "robert", "maria", "peter", "paul", "mark", "tom",
+    "mom", "dad", "mum", "mama", "papa", "grandma", "grandpa", "sister", "brother", "baby",
+    // Common words
+    "the", "and", "for", "you", "our", "with", "this", "that", "one", "two", "three", "four",
+    "five", "six", "seven", "eight", "nine", "ten", "red", "blue", "green", "black", "white",
+    "cat", "dog", "sun", "moon", "star", "sky", "sea", "tree", "house", "car", "book", "coffee",
+    "tea", "lunch", "dinner", "morning", "night", "monday", "tuesday", "wednesday", "thursday",
+    "friday", "saturday", "sunday", "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december", "spring", "summer",
+    "autumn", "winter", "happy", "good", "best", "super", "cool", "magic", "apple", "banana",
+    "orange", "cherry", "pizza", "tiger", "lion", "bear", "wolf", "fox", "bird", "fish",
+    "water", "fire", "earth", "wind", "rock", "music", "game", "games", "play", "time", "life",
+    "world", "hope", "king", "queen", "prince", "angel", "heart", "secure", "safe", "key",
+];
+
+/// Keyboard rows and alphabets, for sequence detection
+const SEQUENCES: &[&str] = &[
+    "abcdefghijklmnopqrstuvwxyz", "qwertyuiop", "asdfghjkl", "zxcvbnm", "0123456789",
+];
+
+/// estimate_code_strength(code)
+/// What: Estimates how many guesses a wordlist + pattern attack needs for a code
+/// Why: Custom codes like "alice-bob-standup" are fine, "a" or "password1" are not;
+///      character-count entropy alone would rate "aaaaaaaaaaaa" highly
+/// Used by: SettingsStore target validation, Tauri check_code_strength command
+/// Contract:
+///   - Case-insensitive; separators (- _ . space) cost nothing
+///   - Each word-like token is charged the cheapest of: listed words (leetspeak
+///     undone, reversed), repeats, keyboard/alphabet sequences, years,
+///     or per-character brute force; a repeated token costs ~1 bit
+///   - Generated codes (generate_code_base32_100b) always rate Strong
+pub fn estimate_code_strength(code: &str) -> CodeStrengthEstimate {
+    let mut seen: Vec<String> = Vec::new();
+    let mut bits = 0.0;
+
+    for token in code.split(|c: char| c == '-' || c == '_' || c == '.' || c.is_whitespace()) {
+        if token.is_empty() {
+            continue;
+        }
+        let lower = token.to_lowercase();
+        if seen.contains(&lower) {
+            bits += 1.0;
+            continue;
+        }
+
+        bits += token_bits(&lower);
+        // Mixed case inside a token buys a little, not a whole extra charset
+        if token.chars().any(char::is_uppercase) && token.chars().any(char::is_lowercase) {
+            bits += 1.0;
+        }
+        seen.push(lower);
+    }
+
+    let bits = (bits * 10.0).floor() / 10.0;
+    let strength = if bits >= STRONG_CODE_BITS {
+        CodeStrength::Strong
+    } else if bits >= OK_CODE_BITS {
+        CodeStrength::Ok
+    } else {
+        CodeStrength::Weak
+    };
+    CodeStrengthEstimate { bits, strength }
+}
+
+/// Cheapest explanation of one separator-free token
+fn token_bits(token: &str) -> f64 {
+    if let Some(bits) = word_bits(token) {
+        return bits;
+    }
+
+    // Otherwise split into letter/digit/other runs and charge each
+    let chars: Vec<char> = token.chars().collect();
+    let mut bits = 0.0;
+    let mut start = 0;
+    while start < chars.len() {
+        let class = char_class(chars[start]);
+        let end = (start..chars.len()).find(|&i| char_class(chars[i]) != class).unwrap_or(chars.len());
+        let run: String = chars[start..end].iter().collect();
+        bits += run_bits(&run, class);
+        start = end;
+    }
+    bits
+}
+
+/// A listed word, leetspeak variant or reversed word
+fn word_bits(token: &str) -> Option<f64> {
+    let unleet: String = token.chars().map(|c| match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        other => other,
+    }).collect();
+    let reversed: String = token.chars().rev().collect();
+
+    if is_word(token) {
+        Some(WORD_BITS)
+    } else if is_word(&unleet) || is_word(&reversed) {
+        Some(WORD_BITS + 1.0)
+    } else {
+        None
+    }
+}
+
+fn is_word(candidate: &str) -> bool {
+    COMMON_WORDS.contains(&candidate)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CharClass {
+    Letter,
+    Digit,
+    Other,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_alphabetic() {
+        CharClass::Letter
+    } else if c.is_ascii_digit() {
+        CharClass::Digit
+    } else {
+        CharClass::Other
+    }
+}
+
+fn charset_bits(class: CharClass) -> f64 {
+    match class {
+        CharClass::Letter => 26f64.log2(),
+        CharClass::Digit => 10f64.log2(),
+        CharClass::Other => 33f64.log2(),
+    }
+}
+
+/// Bits for a run of one character class
+fn run_bits(run: &str, class: CharClass) -> f64 {
+    let len = run.chars().count();
+    let per_char = charset_bits(class);
+    let mut best = per_char * len as f64;
+
+    // "aaaa": which char, how many
+    let first = run.chars().next();
+    if len >= 3 && run.chars().all(|c| Some(c) == first) {
+        best = best.min(per_char + (len as f64).log2());
+    }
+    // "abcd", "4321", "qwerty": which sequence, where, how long, which direction
+    if len >= 3 && is_sequence(run) {
+        best = best.min(per_char + (len as f64).log2() + 1.0);
+    }
+    // "1987", "2024": a plausible year
+    if class == CharClass::Digit && len == 4 && matches!(run.parse::<u32>(), Ok(1900..=2099)) {
+        best = best.min(200f64.log2());
+    }
+    // "alicebob", "standupxyz": listed words glued together plus leftovers
+    if class == CharClass::Letter {
+        best = best.min(segmented_bits(run, per_char));
+    }
+    best
+}
+
+fn is_sequence(run: &str) -> bool {
+    let reversed: String = run.chars().rev().collect();
+    SEQUENCES.iter().any(|seq| seq.contains(run) || seq.contains(reversed.as_str()))
+}
+
+/// Cheapest split of a letter run into listed words and single letters
+fn segmented_bits(run: &str, per_char: f64) -> f64 {
+    let chars: Vec<char> = run.chars().collect();
+    let mut cost = vec![f64::INFINITY; chars.len() + 1];
+    cost[0] = 0.0;
+    for end in 1..=chars.len() {
+        cost[end] = cost[end - 1] + per_char;
+        for start in 0..end.saturating_sub(2) {
+            let piece: String = chars[start..end].iter().collect();
+            if is_word(&piece) {
+                cost[end] = cost[end].min(cost[start] + WORD_BITS);
+            }
+        }
+    }
+    cost[chars.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,6 +347,19 @@ mod tests {
         assert_eq!(generated_codes.len(), SAMPLE_SIZE);
     }
     
+    /// Test: Generated ids have the prefix and enough randomness
+    /// Why: Ids are merged across machines by bundle import
+    #[test]
+    fn test_generate_id() {
+        let id = generate_id("tg");
+        assert!(id.starts_with("tg_"), "Missing prefix: {}", id);
+        assert_eq!(id.len(), 3 + ID_RANDOM_CHARS);
+        assert!(id[3..].chars().all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c)));
+        
+        let ids: HashSet<String> = (0..10_000).map(|_| generate_id("tg")).collect();
+        assert_eq!(ids.len(), 10_000, "Duplicate id generated");
+    }
+    
     /// Test: Code format matches specification
     /// Why: Other components parse this exact format
     #[test]
@@ -275,4 +560,62 @@ mod tests {
             "Not enough variety in first character: {} different chars", first_chars.len()
         );
     }
+    
+    /// Test: Generated codes always rate Strong
+    /// Why: Validation must never reject the app's own codes
+    #[test]
+    fn test_generated_codes_are_strong() {
+        for _ in 0..500 {
+            let code = generate_code_base32_100b();
+            let estimate = estimate_code_strength(&code);
+            assert_eq!(estimate.strength, CodeStrength::Strong, "{} rated {:?}", code, estimate);
+        }
+    }
+    
+    /// Test: Memorable word codes are Ok, common patterns are Weak
+    #[test]
+    fn test_custom_code_classification() {
+        let strength = |code: &str| estimate_code_strength(code).strength;
+        
+        assert_eq!(strength("alice-bob-standup"), CodeStrength::Ok);
+        assert_eq!(strength("purple-walrus-jumps-quietly"), CodeStrength::Strong);
+        
+        assert_eq!(strength("password123"), CodeStrength::Weak);
+        assert_eq!(strength("p4ssw0rd-2024"), CodeStrength::Weak);
+        assert_eq!(strength("qwerty-asdf-1234"), CodeStrength::Weak);
+        assert_eq!(strength("alicebob"), CodeStrength::Weak);
+    }
+    
+    /// Test: Patterns cost far less than their length suggests
+    #[test]
+    fn test_patterns_are_cheap() {
+        let bits = |code: &str| estimate_code_strength(code).bits;
+        
+        assert!(bits("aaaaaaaaaaaaaaaa") < 10.0);
+        assert!(bits("abcdefghijklmnop") < 10.0);
+        assert!(bits("drowssap") < 15.0, "reversed words are in the dictionary");
+        assert!(bits("1987") < 8.0);
+        // Repeating a token adds almost nothing
+        assert!(bits("xkcd-xkcd-xkcd") < bits("xkcd") + 3.0);
+        // Separators are free; capitalizing a token buys one bit
+        assert_eq!(bits("Alice-Bob"), bits("alice_bob") + 2.0);
+    }
+    
+    // Edge case tests
+    
+    #[test]
+    fn test_empty_and_tiny_codes_are_weak() {
+        assert_eq!(estimate_code_strength("").bits, 0.0);
+        assert_eq!(estimate_code_strength("---").strength, CodeStrength::Weak);
+        assert_eq!(estimate_code_strength("a").strength, CodeStrength::Weak);
+        assert_eq!(estimate_code_strength("ß€漢").strength, CodeStrength::Weak);
+    }
+    
+    #[test]
+    fn test_strength_ordering_and_serde() {
+        assert!(CodeStrength::Weak < CodeStrength::Ok && CodeStrength::Ok < CodeStrength::Strong);
+        assert_eq!(serde_json::to_string(&CodeStrength::Strong).unwrap(), "\"strong\"");
+        assert_eq!(serde_json::from_str::<CodeStrength>("\"weak\"").unwrap(), CodeStrength::Weak);
+        assert_eq!(CodeStrength::default(), CodeStrength::Ok);
+    }
 }
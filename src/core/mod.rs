@@ -8,10 +8,21 @@ pub mod crypto;
 pub mod room;
 pub mod platform;
 pub mod call_state;
+pub mod cipher;
+pub mod secret;
+pub mod timestamp;
+pub mod link;
+pub mod browser;
+pub mod window_placement;
 
 // Re-export main functions for cleaner imports
 // Usage: use justcall::core::{generate_code_base32_100b, room_id_from_code, get_default_keybinds};
-pub use crypto::generate_code_base32_100b;
-pub use room::room_id_from_code;
-pub use platform::{get_default_keybinds, get_platform_name, get_platform_capabilities};
+pub use crypto::{estimate_code_strength, generate_code_base32_100b, generate_id, CodeStrength, CodeStrengthEstimate};
+pub use room::{room_id_from_code, channel_room_id, normalize_channel_name, meeting_url};
+pub use platform::{get_default_keybinds, get_platform_name, get_platform_capabilities, get_url_opener};
 pub use call_state::CallState;
+pub use secret::{Secret, scrub_secrets};
+pub use timestamp::Timestamp;
+pub use link::{parse_meeting_link, LinkProvider, MeetingLink};
+pub use browser::{browser_command, browser_family, BrowserFamily};
+pub use window_placement::{mini_window, place_window, MonitorArea, Rect};
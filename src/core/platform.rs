@@ -151,6 +151,22 @@ pub fn get_platform_capabilities() -> PlatformCapabilities {
     }
 }
 
+/// get_url_opener()
+/// What: Returns the OS command (program + leading args) that opens a URL
+/// Why: Headless/CLI joins launch the meeting without the Tauri shell plugin
+/// Used by:
+///   - CLI `blink join` (default browser, or fallback when the configured one fails)
+pub fn get_url_opener() -> &'static [&'static str] {
+    #[cfg(target_os = "macos")]
+    { &["open"] }
+    
+    #[cfg(target_os = "windows")]
+    { &["cmd", "/C", "start", ""] }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    { &["xdg-open"] }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,6 +344,15 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_url_opener() {
+        let opener = get_url_opener();
+        assert!(!opener.is_empty());
+        
+        #[cfg(target_os = "linux")]
+        assert_eq!(opener, ["xdg-open"]);
+    }
+    
     #[test]
     fn test_platform_name_matches_cfg() {
         let name = get_platform_name();
@@ -44,6 +44,63 @@ pub fn room_id_from_code(code: &str) -> String {
     format!("JustCallRoom{}", &encoded[..8])
 }
 
+/// normalize_channel_name(name)
+/// What: Canonical form of a channel name: trimmed, lowercase, whitespace runs -> "-"
+/// Why: "Stand Up" typed by one partner and "stand up" by the other must meet
+/// Used by: channel_room_id(), SettingsStore channel validation
+pub fn normalize_channel_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// channel_room_id(code, channel)
+/// What: Derives the room for a named channel (sub-room) of a pairing code
+/// Why: Partners sharing one code can hold separate calls ("standup", "pairing")
+///      without exchanging a new code
+/// Contract:
+///   - Same format as room_id_from_code(): "JustCallRoom" + 8 chars
+///   - Hyphens in the code are ignored, the channel name is normalized
+///   - An empty (normalized) channel name is the code's default room
+///   - Different domain separator and a length prefix, so no (code, channel)
+///     pair derives another code's default room by concatenation
+/// Used by:
+///   - Tauri JoinChannel hotkey and tray menu
+///   - CLI `blink join --channel`
+/// Change notes: Format must stay consistent or existing channels can't connect
+pub fn channel_room_id(code: &str, channel: &str) -> String {
+    let channel = normalize_channel_name(channel);
+    if channel.is_empty() {
+        return room_id_from_code(code);
+    }
+    let clean_code = code.replace('-', "");
+    
+    let mut hasher = Sha256::new();
+    hasher.update(b"justcall-channel-v1|");
+    hasher.update((clean_code.len() as u64).to_be_bytes());
+    hasher.update(clean_code.as_bytes());
+    hasher.update(channel.as_bytes());
+    let hash_result = hasher.finalize();
+    
+    let encoded = BASE32_NOPAD.encode(&hash_result).to_lowercase();
+    format!("JustCallRoom{}", &encoded[..8])
+}
+
+/// Base URL of the video provider rooms are hosted on
+/// Change notes: Must match what the Tauri app opens or partners end up apart
+pub const MEETING_BASE_URL: &str = "https://meet.jit.si";
+
+/// meeting_url(room_id)
+/// What: Builds the full provider URL for a derived room
+/// Why: CLI and app must open the exact same URL for a room
+/// Used by:
+///   - ExternalBrowserService::open_meeting() (src-tauri)
+///   - CLI `blink join`
+pub fn meeting_url(room_id: &str) -> String {
+    format!("{}/{}", MEETING_BASE_URL, room_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +157,29 @@ mod tests {
         assert_eq!(room, room2);
     }
     
+    #[test]
+    fn test_meeting_url() {
+        let room = room_id_from_code("test-code");
+        assert_eq!(meeting_url(&room), format!("https://meet.jit.si/{}", room));
+    }
+    
+    #[test]
+    fn test_channel_rooms() {
+        let code = "abcd-efgh-ijkl-mnop-qrst";
+        let standup = channel_room_id(code, "standup");
+        let pairing = channel_room_id(code, "pairing");
+        
+        assert_ne!(standup, pairing);
+        assert_ne!(standup, room_id_from_code(code));
+        assert_ne!(standup, channel_room_id("other-code", "standup"));
+        assert!(standup.starts_with("JustCallRoom"));
+        assert_eq!(standup.len(), 20);
+        
+        // Both partners meet regardless of hyphens and how they typed the name
+        assert_eq!(standup, channel_room_id("abcdefghijklmnopqrst", "  Standup "));
+        assert_eq!(channel_room_id(code, "Stand Up"), channel_room_id(code, "stand  up"));
+    }
+    
     // Edge case tests
     
     #[test]
@@ -243,4 +323,17 @@ mod tests {
             );
         }
     }
+    
+    #[test]
+    fn test_empty_channel_is_default_room() {
+        assert_eq!(channel_room_id("test-code", ""), room_id_from_code("test-code"));
+        assert_eq!(channel_room_id("test-code", "   "), room_id_from_code("test-code"));
+    }
+    
+    #[test]
+    fn test_channel_does_not_alias_other_codes() {
+        // Splitting the same characters differently between code and channel
+        assert_ne!(channel_room_id("abc", "def"), channel_room_id("ab", "cdef"));
+        assert_ne!(channel_room_id("abc", "def"), room_id_from_code("abcdef"));
+    }
 }
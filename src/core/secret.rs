@@ -0,0 +1,229 @@
+// Secret values and log redaction
+// What: Secret<T> wrapper whose Debug/Display never print the value, plus a
+//       scrubber for pairing codes and rooms embedded in free text
+// Why: Pairing codes are the only thing standing between a stranger and a private room;
+//      a stray {:?} or log line must not leak them
+// Used by:
+//   - models::Target.code
+//   - Tauri log plugin formatter (scrub_secrets)
+// Change notes: If the code format in crypto.rs or the room prefix in room.rs changes,
+//               update the scanner below
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Placeholder printed instead of a secret
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Prefix of derived room names (see room_id_from_code)
+const ROOM_PREFIX: &str = "JustCallRoom";
+const ROOM_SUFFIX_LEN: usize = 8;
+
+/// Wrapper for values that must not appear in logs
+/// What: Serializes transparently, but Debug/Display print [REDACTED]
+/// Why: settings.json still round-trips while `{:?}` on a Target stays safe
+/// Used by: Target.code
+/// Contract:
+///   - expose() is the only way to read the value - grep for it when auditing
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Borrow the wrapped value
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({})", REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl From<String> for Secret<String> {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl From<&str> for Secret<String> {
+    fn from(value: &str) -> Self {
+        Secret(value.to_string())
+    }
+}
+
+/// scrub_secrets(text)
+/// What: Replaces pairing-code-shaped tokens and derived room names with [REDACTED]
+/// Why: Last line of defense for log messages that format codes by hand
+/// Used by: Tauri log plugin formatter
+/// Contract:
+///   - Codes: five hyphen-separated groups of four base32 chars (any case), as
+///     produced by generate_code_base32_100b(), not glued to other alphanumerics
+///   - Rooms: "JustCallRoom" + 8 base32 chars
+///   - Returns Cow::Borrowed when nothing matched (no allocation on the hot path)
+pub fn scrub_secrets(text: &str) -> Cow<'_, str> {
+    let bytes = text.as_bytes();
+    let mut output: Option<String> = None;
+    let mut copied_up_to = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let at_boundary = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+        let matched = if at_boundary {
+            match_code(&bytes[i..]).or_else(|| match_room(&bytes[i..]))
+        } else {
+            None
+        };
+
+        match matched {
+            Some(len) => {
+                let out = output.get_or_insert_with(|| String::with_capacity(text.len()));
+                out.push_str(&text[copied_up_to..i]);
+                out.push_str(REDACTED);
+                i += len;
+                copied_up_to = i;
+            }
+            None => i += 1,
+        }
+    }
+
+    match output {
+        Some(mut out) => {
+            out.push_str(&text[copied_up_to..]);
+            Cow::Owned(out)
+        }
+        None => Cow::Borrowed(text),
+    }
+}
+
+fn is_base32(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || (b'2'..=b'7').contains(&byte)
+}
+
+/// Length of the token if it ends here (next byte is not alphanumeric)
+fn ends_token(bytes: &[u8], len: usize) -> Option<usize> {
+    match bytes.get(len) {
+        Some(next) if next.is_ascii_alphanumeric() => None,
+        _ => Some(len),
+    }
+}
+
+fn match_code(bytes: &[u8]) -> Option<usize> {
+    const GROUPS: usize = 5;
+    const GROUP_LEN: usize = 4;
+    let len = GROUPS * GROUP_LEN + (GROUPS - 1);
+    if bytes.len() < len {
+        return None;
+    }
+
+    for (pos, &byte) in bytes[..len].iter().enumerate() {
+        let is_separator = pos % (GROUP_LEN + 1) == GROUP_LEN;
+        let valid = if is_separator { byte == b'-' } else { is_base32(byte) };
+        if !valid {
+            return None;
+        }
+    }
+    ends_token(bytes, len)
+}
+
+fn match_room(bytes: &[u8]) -> Option<usize> {
+    let len = ROOM_PREFIX.len() + ROOM_SUFFIX_LEN;
+    if bytes.len() < len || !bytes.starts_with(ROOM_PREFIX.as_bytes()) {
+        return None;
+    }
+    if !bytes[ROOM_PREFIX.len()..len].iter().all(|&b| is_base32(b)) {
+        return None;
+    }
+    ends_token(bytes, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{generate_code_base32_100b, room_id_from_code};
+
+    #[test]
+    fn test_secret_redacts() {
+        let secret = Secret::from("abcd-efgh-ijkl-mnop-qrst");
+        assert_eq!(format!("{}", secret), "[REDACTED]");
+        assert_eq!(format!("{:?}", secret), "Secret([REDACTED])");
+        assert_eq!(secret.expose(), "abcd-efgh-ijkl-mnop-qrst");
+    }
+
+    #[test]
+    fn test_secret_serde_transparent() {
+        let secret = Secret::from("abcd-efgh");
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(json, "\"abcd-efgh\"");
+
+        let back: Secret<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, secret);
+    }
+
+    #[test]
+    fn test_scrub_generated_code_and_room() {
+        let code = generate_code_base32_100b();
+        let room = room_id_from_code(&code);
+        let line = format!("Generated room ID from code '{}': '{}'", code, room);
+
+        let scrubbed = scrub_secrets(&line);
+        assert_eq!(scrubbed, "Generated room ID from code '[REDACTED]': '[REDACTED]'");
+        assert!(!scrubbed.contains(&code));
+        assert!(!scrubbed.contains(&room));
+    }
+
+    #[test]
+    fn test_scrub_url_and_uppercase() {
+        let scrubbed = scrub_secrets("Opening https://meet.jit.si/JustCallRoomabcdefgh and F7RX-KQ3M-27P6-Z4NH-TD5W");
+        assert_eq!(scrubbed, "Opening https://meet.jit.si/[REDACTED] and [REDACTED]");
+    }
+
+    #[test]
+    fn test_scrub_leaves_clean_text_borrowed() {
+        let text = "Hotkey registered: Ctrl+Alt+J";
+        assert!(matches!(scrub_secrets(text), Cow::Borrowed(_)));
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_scrub_near_misses() {
+        // Wrong group sizes, non-base32 digits, glued to other text
+        for text in [
+            "abcd-efgh-ijkl-mnop",
+            "abcd-efgh-ijkl-mnop-qrs",
+            "abcd-efgh-ijkl-mnop-qrs1",
+            "xabcd-efgh-ijkl-mnop-qrst",
+            "abcd-efgh-ijkl-mnop-qrstu",
+            "JustCallRoomabc",
+            "JustCallRoomabcdefghi",
+            "",
+        ] {
+            assert_eq!(scrub_secrets(text), text, "Unexpectedly scrubbed: {}", text);
+        }
+    }
+
+    #[test]
+    fn test_scrub_multibyte_text() {
+        let scrubbed = scrub_secrets("é abcd-efgh-ijkl-mnop-qrst ü");
+        assert_eq!(scrubbed, "é [REDACTED] ü");
+    }
+}
@@ -0,0 +1,335 @@
+// RFC 3339 timestamps
+// What: UTC timestamp type that serializes as "YYYY-MM-DDTHH:MM:SS[.mmm]Z"
+// Why: Target.created_at was a free-form String; sorting and display need real times
+// Used by:
+//   - models::Target.created_at
+//   - SettingsStore::create_target() (Timestamp::now)
+// Change notes: Deserialization never fails - unparseable values are kept verbatim
+//               so one bad field can't make the whole settings file unreadable
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A point in time, or a legacy value we couldn't parse
+/// What: Milliseconds since the Unix epoch (UTC) when valid
+/// Why: Keeps invalid legacy strings round-tripping instead of erroring on load
+/// Used by: Target.created_at
+/// Contract:
+///   - Valid values always display/serialize in canonical UTC "Z" form
+///   - Invalid values display/serialize exactly as they were read
+///   - Ordering: valid values by time, invalid values sort last
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Timestamp(Repr);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    Valid { unix_millis: i64 },
+    Invalid(String),
+}
+
+/// Error for strict parsing (Timestamp::from_str)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTimestamp(pub String);
+
+impl fmt::Display for InvalidTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid RFC 3339 timestamp '{}'", self.0)
+    }
+}
+
+impl std::error::Error for InvalidTimestamp {}
+
+impl Timestamp {
+    /// Current time
+    pub fn now() -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        Self::from_unix_millis(millis)
+    }
+
+    pub fn from_unix_millis(unix_millis: i64) -> Self {
+        Timestamp(Repr::Valid { unix_millis })
+    }
+
+    /// parse_lenient(value)
+    /// What: Parses like from_str(), but keeps unparseable input as an invalid Timestamp
+    /// Used by: Deserialize
+    pub fn parse_lenient(value: &str) -> Self {
+        value.parse().unwrap_or_else(|_| Timestamp(Repr::Invalid(value.to_string())))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self.0, Repr::Valid { .. })
+    }
+
+    /// Milliseconds since the epoch, None for invalid values
+    pub fn unix_millis(&self) -> Option<i64> {
+        match self.0 {
+            Repr::Valid { unix_millis } => Some(unix_millis),
+            Repr::Invalid(_) => None,
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = InvalidTimestamp;
+
+    /// Accepts RFC 3339 ("2024-01-01T10:00:00Z", "...T10:00:00.123+02:00"),
+    /// a space or lowercase 't'/'z', and legacy date-only values ("2024-01-01")
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_rfc3339(value.trim())
+            .map(Self::from_unix_millis)
+            .ok_or_else(|| InvalidTimestamp(value.to_string()))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unix_millis = match &self.0 {
+            Repr::Valid { unix_millis } => *unix_millis,
+            Repr::Invalid(raw) => return f.write_str(raw),
+        };
+
+        let secs = unix_millis.div_euclid(1000);
+        let millis = unix_millis.rem_euclid(1000);
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        let rem = secs.rem_euclid(86_400);
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year, month, day, rem / 3600, (rem % 3600) / 60, rem % 60
+        )?;
+        if millis != 0 {
+            write!(f, ".{:03}", millis)?;
+        }
+        f.write_str("Z")
+    }
+}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Repr::Valid { unix_millis: a }, Repr::Valid { unix_millis: b }) => a.cmp(b),
+            (Repr::Valid { .. }, Repr::Invalid(_)) => Ordering::Less,
+            (Repr::Invalid(_), Repr::Valid { .. }) => Ordering::Greater,
+            (Repr::Invalid(a), Repr::Invalid(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Timestamp::parse_lenient(&raw))
+    }
+}
+
+/// "YYYY-MM-DD[(T|t| )HH:MM:SS[.fff][Z|z|±HH:MM]]" -> unix millis
+fn parse_rfc3339(value: &str) -> Option<i64> {
+    let bytes = value.as_bytes();
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = bytes.get(range)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        std::str::from_utf8(digits).ok()?.parse().ok()
+    };
+
+    let year = number(0..4)?;
+    let month = number(5..7)?;
+    let day = number(8..10)?;
+    if bytes.get(4) != Some(&b'-') || bytes.get(7) != Some(&b'-') {
+        return None;
+    }
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+
+    // Legacy date-only value: midnight UTC
+    if bytes.len() == 10 {
+        return Some(days * 86_400_000);
+    }
+
+    if !matches!(bytes.get(10), Some(b'T' | b't' | b' ')) {
+        return None;
+    }
+    let hour = number(11..13)?;
+    let minute = number(14..16)?;
+    let second = number(17..19)?;
+    if bytes.get(13) != Some(&b':') || bytes.get(16) != Some(&b':') {
+        return None;
+    }
+    // 60 allows a leap second; it folds into the next minute
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut pos = 19;
+    let mut millis = 0;
+    if bytes.get(pos) == Some(&b'.') {
+        let start = pos + 1;
+        let end = start + bytes[start..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if end == start {
+            return None;
+        }
+        // Keep millisecond precision, ignore finer digits
+        let digits = &value[start..end.min(start + 3)];
+        millis = digits.parse::<i64>().ok()? * 10_i64.pow(3 - digits.len() as u32);
+        pos = end;
+    }
+
+    let offset_secs = match &bytes[pos..] {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), ..] if bytes.len() == pos + 6 && bytes[pos + 3] == b':' => {
+            let hours = number(pos + 1..pos + 3)?;
+            let minutes = number(pos + 4..pos + 6)?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'+' { offset } else { -offset }
+        }
+        _ => return None,
+    };
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    Some(secs * 1000 + millis)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of days_from_civil()
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(value: &str) -> Timestamp {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_canonical_round_trip() {
+        for value in ["2024-01-01T00:00:00Z", "1999-12-31T23:59:59Z", "2024-02-29T12:30:05.120Z"] {
+            assert_eq!(ts(value).to_string(), value);
+        }
+        assert_eq!(ts("1970-01-01T00:00:00Z").unix_millis(), Some(0));
+    }
+
+    #[test]
+    fn test_accepts_offsets_and_legacy_forms() {
+        let utc = ts("2024-06-01T10:00:00Z");
+        assert_eq!(ts("2024-06-01T12:00:00+02:00"), utc);
+        assert_eq!(ts("2024-06-01T05:30:00-04:30"), utc);
+        assert_eq!(ts("2024-06-01 10:00:00z"), utc);
+        assert_eq!(ts("2024-06-01t10:00:00.000Z"), utc);
+        assert_eq!(ts("2024-06-01").to_string(), "2024-06-01T00:00:00Z");
+        // JavaScript toISOString() output
+        assert_eq!(ts("2024-06-01T10:00:00.123Z").unix_millis(), Some(utc.unix_millis().unwrap() + 123));
+    }
+
+    #[test]
+    fn test_now_is_canonical() {
+        let now = Timestamp::now();
+        assert!(now.is_valid());
+        assert_eq!(ts(&now.to_string()), now);
+        assert!(now > ts("2024-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_serde_lenient() {
+        let valid: Timestamp = serde_json::from_str("\"2024-01-01T00:00:00+00:00\"").unwrap();
+        assert_eq!(serde_json::to_string(&valid).unwrap(), "\"2024-01-01T00:00:00Z\"");
+
+        // Invalid values load and round-trip unchanged
+        let legacy: Timestamp = serde_json::from_str("\"last tuesday\"").unwrap();
+        assert!(!legacy.is_valid());
+        assert_eq!(serde_json::to_string(&legacy).unwrap(), "\"last tuesday\"");
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_rejects_malformed() {
+        for value in [
+            "",
+            "2024-13-01T00:00:00Z",
+            "2023-02-29T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T00:00:00",
+            "2024-01-01T00:00:00.Z",
+            "2024-01-01T00:00:00+0200",
+            "2024/01/01",
+            "24-01-01",
+            "２０２４-01-01",
+        ] {
+            assert!(value.parse::<Timestamp>().is_err(), "Accepted: {}", value);
+        }
+    }
+
+    #[test]
+    fn test_ordering_and_pre_epoch() {
+        let old = ts("1969-12-31T23:59:59.500Z");
+        assert_eq!(old.unix_millis(), Some(-500));
+        assert_eq!(old.to_string(), "1969-12-31T23:59:59.500Z");
+
+        let invalid = Timestamp::parse_lenient("unknown");
+        let mut values = [invalid.clone(), ts("2024-01-01T00:00:00Z"), old.clone()];
+        values.sort();
+        assert_eq!(values.first(), Some(&old));
+        assert_eq!(values.last(), Some(&invalid));
+    }
+}
@@ -0,0 +1,164 @@
+// Window placement
+// What: Fits a remembered window rectangle onto the monitors that exist now,
+//       and computes the compact corner rectangle of the mini (PiP) mode
+// Why: A saved position on an unplugged monitor would open the call window
+//      off-screen; monitors also change size and arrangement between calls
+// Used by: Tauri ConferenceWindow (restore on open, mini mode toggle)
+// Change notes: Everything is in physical pixels, like Tauri's monitor API
+
+/// A window or monitor rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    fn center(&self) -> (i64, i64) {
+        (self.x as i64 + self.width as i64 / 2, self.y as i64 + self.height as i64 / 2)
+    }
+
+    fn contains_point(&self, (x, y): (i64, i64)) -> bool {
+        x >= self.x as i64
+            && y >= self.y as i64
+            && x < self.x as i64 + self.width as i64
+            && y < self.y as i64 + self.height as i64
+    }
+}
+
+/// A connected monitor
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorArea {
+    pub name: Option<String>,
+    pub area: Rect,
+    pub scale_factor: f64,
+}
+
+/// Mini window size and distance from the screen edges (logical pixels)
+const MINI_WIDTH: f64 = 320.0;
+const MINI_HEIGHT: f64 = 180.0;
+const MINI_MARGIN: f64 = 16.0;
+
+/// place_window(saved, saved_monitor, monitors)
+/// What: Where to put a window last seen at `saved` (on `saved_monitor`)
+/// Contract:
+///   - monitors[0] is the primary monitor; None when there are no monitors
+///   - The remembered monitor (by name) wins; otherwise the monitor holding
+///     the window's center; otherwise the primary monitor, centered
+///   - The result always lies fully on the chosen monitor (shrunk if needed)
+pub fn place_window(saved: Rect, saved_monitor: Option<&str>, monitors: &[MonitorArea]) -> Option<Rect> {
+    let by_name = saved_monitor
+        .and_then(|name| monitors.iter().find(|m| m.name.as_deref() == Some(name)));
+    let by_center = || monitors.iter().find(|m| m.area.contains_point(saved.center()));
+
+    match by_name.or_else(by_center) {
+        Some(monitor) => Some(clamp_to(saved, monitor.area)),
+        None => monitors.first().map(|primary| center_on(saved, primary.area)),
+    }
+}
+
+/// mini_window(monitor)
+/// What: Bottom-right corner thumbnail on the given monitor
+pub fn mini_window(monitor: &MonitorArea) -> Rect {
+    let scale = if monitor.scale_factor > 0.0 { monitor.scale_factor } else { 1.0 };
+    let width = ((MINI_WIDTH * scale) as u32).min(monitor.area.width);
+    let height = ((MINI_HEIGHT * scale) as u32).min(monitor.area.height);
+    let margin = (MINI_MARGIN * scale) as i64;
+
+    let right = monitor.area.x as i64 + monitor.area.width as i64;
+    let bottom = monitor.area.y as i64 + monitor.area.height as i64;
+    let x = (right - width as i64 - margin).max(monitor.area.x as i64);
+    let y = (bottom - height as i64 - margin).max(monitor.area.y as i64);
+    Rect { x: x as i32, y: y as i32, width, height }
+}
+
+fn clamp_to(rect: Rect, area: Rect) -> Rect {
+    let width = rect.width.min(area.width);
+    let height = rect.height.min(area.height);
+    let max_x = area.x as i64 + (area.width - width) as i64;
+    let max_y = area.y as i64 + (area.height - height) as i64;
+    Rect {
+        x: (rect.x as i64).clamp(area.x as i64, max_x) as i32,
+        y: (rect.y as i64).clamp(area.y as i64, max_y) as i32,
+        width,
+        height,
+    }
+}
+
+fn center_on(rect: Rect, area: Rect) -> Rect {
+    let width = rect.width.min(area.width);
+    let height = rect.height.min(area.height);
+    Rect {
+        x: area.x + ((area.width - width) / 2) as i32,
+        y: area.y + ((area.height - height) / 2) as i32,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32, width: u32) -> MonitorArea {
+        MonitorArea {
+            name: Some(name.to_string()),
+            area: Rect { x, y: 0, width, height: 1080 },
+            scale_factor: 1.0,
+        }
+    }
+
+    const SAVED: Rect = Rect { x: 2200, y: 100, width: 1024, height: 768 };
+
+    #[test]
+    fn test_restores_on_remembered_monitor() {
+        let monitors = [monitor("eDP-1", 0, 1920), monitor("HDMI-1", 1920, 1920)];
+        assert_eq!(place_window(SAVED, Some("HDMI-1"), &monitors), Some(SAVED));
+    }
+
+    #[test]
+    fn test_missing_monitor_falls_back_to_primary() {
+        let monitors = [monitor("eDP-1", 0, 1920)];
+        assert_eq!(
+            place_window(SAVED, Some("HDMI-1"), &monitors),
+            Some(Rect { x: 448, y: 156, width: 1024, height: 768 })
+        );
+    }
+
+    #[test]
+    fn test_mini_window_in_corner() {
+        let mut hidpi = monitor("HDMI-1", 1920, 3840);
+        hidpi.area.height = 2160;
+        hidpi.scale_factor = 2.0;
+        assert_eq!(mini_window(&hidpi), Rect { x: 1920 + 3840 - 640 - 32, y: 2160 - 360 - 32, width: 640, height: 360 });
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_unknown_monitor_name_uses_window_center() {
+        // Monitor renamed (new cable/driver) but still in the same place
+        let monitors = [monitor("eDP-1", 0, 1920), monitor("DP-2", 1920, 1920)];
+        assert_eq!(place_window(SAVED, Some("HDMI-1"), &monitors), Some(SAVED));
+    }
+
+    #[test]
+    fn test_clamps_into_smaller_monitor() {
+        let monitors = [monitor("eDP-1", 0, 1280)];
+        let placed = place_window(Rect { x: 900, y: 700, width: 1600, height: 900 }, Some("eDP-1"), &monitors).unwrap();
+        assert_eq!(placed, Rect { x: 0, y: 180, width: 1280, height: 900 });
+    }
+
+    #[test]
+    fn test_no_monitors() {
+        assert_eq!(place_window(SAVED, None, &[]), None);
+    }
+
+    #[test]
+    fn test_mini_window_on_tiny_monitor() {
+        let tiny = MonitorArea { name: None, area: Rect { x: 0, y: 0, width: 200, height: 100 }, scale_factor: 0.0 };
+        assert_eq!(mini_window(&tiny), Rect { x: 0, y: 0, width: 200, height: 100 });
+    }
+}
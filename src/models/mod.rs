@@ -7,4 +7,4 @@
 pub mod settings;
 
 // Re-export main types for convenience
-pub use settings::{Settings, Target, TargetType, CallDefaults};
+pub use settings::{Settings, Target, NewTarget, Channel, TargetType, CallDefaults, CodeEncryption, KeySource, JoinMode, TrayClickAction, NotificationKind, NotificationSettings, BrowserSettings, RecentCall, WindowGeometry};
@@ -7,6 +7,7 @@
 ///   - CallController for runtime config (Phase 5.1)
 
 use serde::{Deserialize, Serialize};
+use crate::core::{browser_command, meeting_url, room_id_from_code, CodeStrength, Rect, Secret, Timestamp};
 
 /// Root settings object containing all configuration
 /// What: Top-level container for all app settings
@@ -25,6 +26,32 @@ pub struct Settings {
     
     /// List of call targets (people/groups)
     pub targets: Vec<Target>,
+    
+    /// Pairing-code encryption at rest (None = codes stored in plaintext)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_encryption: Option<CodeEncryption>,
+    
+    /// Most recent joins, newest first (SettingsStore::record_recent_call)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_calls: Vec<RecentCall>,
+}
+
+impl Settings {
+    /// join_mode_for(target)
+    /// What: Where joining this target opens the call
+    /// Used by: Tauri join path (CallController)
+    /// Contract:
+    ///   - Target's call_defaults.join_mode wins over app_settings.join_mode
+    ///   - Link targets are always External: the conference window only hosts
+    ///     rooms on our Jitsi server
+    pub fn join_mode_for(&self, target: &Target) -> JoinMode {
+        match target.target_type {
+            TargetType::Link => JoinMode::External,
+            TargetType::Person | TargetType::Group => {
+                target.call_defaults.join_mode.unwrap_or(self.app_settings.join_mode)
+            }
+        }
+    }
 }
 
 impl Default for Settings {
@@ -34,10 +61,51 @@ impl Default for Settings {
             app_settings: AppSettings::default(),
             keybinds: Keybinds::default(),
             targets: Vec::new(),
+            code_encryption: None,
+            recent_calls: Vec::new(),
         }
     }
 }
 
+/// A past join
+/// What: Which target (and channel) was joined, and when
+/// Used by: Settings.recent_calls, tray Recent submenu
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecentCall {
+    pub target_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<String>,
+    pub joined_at: Timestamp,
+}
+
+/// Encryption settings for pairing codes on disk
+/// What: Which key protects Target.code values in settings.json
+/// Why: Anyone reading settings.json could otherwise join every room
+/// Used by: SettingsStore load/save (storage::code_encryption)
+/// Change notes: Changing method requires re-encrypting all codes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CodeEncryption {
+    /// Where the key comes from
+    pub method: KeySource,
+    
+    /// Argon2 salt (base64), passphrase method only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    
+    /// Known value sealed with the key; detects a wrong key before touching codes
+    pub verifier: String,
+}
+
+/// Source of the code encryption key
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    /// Random key in a 0600 keyfile next to settings.json
+    Keyfile,
+    /// Key derived from a user passphrase (Argon2id)
+    Passphrase,
+}
+
 /// Global application settings
 /// What: App-wide preferences and behavior settings
 /// Why: Users need to customize app behavior
@@ -60,6 +128,38 @@ pub struct AppSettings {
     #[serde(default = "default_true")]
     pub show_notifications: bool,
     
+    /// Which notifications to show (when show_notifications is on)
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    
+    /// Refuse to load settings other users can read or that another user owns
+    #[serde(default)]
+    pub strict_permissions: bool,
+    
+    /// Weakest custom pairing code accepted when adding/editing targets
+    #[serde(default)]
+    pub min_code_strength: CodeStrength,
+    
+    /// Where calls open unless a target overrides it (CallDefaults.join_mode)
+    #[serde(default)]
+    pub join_mode: JoinMode,
+    
+    /// Browser for external joins (default: the system browser)
+    #[serde(default)]
+    pub browser: BrowserSettings,
+    
+    /// End an embedded call after this many seconds with nobody else in the room (0 = never)
+    #[serde(default = "default_auto_hangup_after_secs")]
+    pub auto_hangup_after_secs: u64,
+    
+    /// Keep a hidden, loaded conference window ready so embedded joins start instantly
+    /// (costs the memory of an idle webview)
+    #[serde(default)]
+    pub prewarm_conference_window: bool,
+    
+    /// What a left-click on the tray icon does (the menu is on right-click)
+    #[serde(default)]
+    pub tray_left_click: TrayClickAction,
 
 }
 
@@ -70,11 +170,136 @@ impl Default for AppSettings {
             always_on_top: true,
             play_join_sound: true,
             show_notifications: true,
+            notifications: NotificationSettings::default(),
+            strict_permissions: false,
+            min_code_strength: CodeStrength::Ok,
+            join_mode: JoinMode::External,
+            browser: BrowserSettings::default(),
+            auto_hangup_after_secs: default_auto_hangup_after_secs(),
+            prewarm_conference_window: false,
+            tray_left_click: TrayClickAction::OpenSettings,
+        }
+    }
+}
+
+impl AppSettings {
+    /// notifies(kind)
+    /// What: Whether a notification of this kind should be shown
+    /// Contract: show_notifications off silences every kind
+    pub fn notifies(&self, kind: NotificationKind) -> bool {
+        self.show_notifications && self.notifications.enabled(kind)
+    }
+}
+
+/// Events the app can notify about
+/// Used by: NotificationSettings, Tauri Notifier
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A call could not be started (window, browser or provider failure)
+    JoinFailed,
+    /// The join-primary hotkey was pressed with no primary target
+    NoPrimaryTarget,
+    /// A global hotkey could not be registered
+    HotkeyFailed,
+    /// Someone joined the embedded call
+    PartnerJoined,
+}
+
+/// Per-event notification toggles
+/// What: One switch per NotificationKind, all on by default
+/// Why: Failures are worth a popup for most users; join announcements in a
+///      busy room may not be
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationSettings {
+    #[serde(default = "default_true")]
+    pub join_failed: bool,
+    #[serde(default = "default_true")]
+    pub no_primary_target: bool,
+    #[serde(default = "default_true")]
+    pub hotkey_failed: bool,
+    #[serde(default = "default_true")]
+    pub partner_joined: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            join_failed: true,
+            no_primary_target: true,
+            hotkey_failed: true,
+            partner_joined: true,
+        }
+    }
+}
 
+impl NotificationSettings {
+    pub fn enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::JoinFailed => self.join_failed,
+            NotificationKind::NoPrimaryTarget => self.no_primary_target,
+            NotificationKind::HotkeyFailed => self.hotkey_failed,
+            NotificationKind::PartnerJoined => self.partner_joined,
         }
     }
 }
 
+/// Browser used for external joins
+/// What: Optional executable, profile and app-window mode
+/// Why: A dedicated (app-mode) window or work profile instead of a tab in
+///      whatever browser happens to be the default
+/// Used by: CLI `join`, Tauri ExternalBrowserService
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BrowserSettings {
+    /// Browser executable (path, name on PATH, or macOS .app); None = system default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executable: Option<String>,
+    
+    /// Profile name ("Profile 1", "work") or profile directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    
+    /// Open a dedicated window (Chromium --app=URL) instead of a tab
+    #[serde(default)]
+    pub app_mode: bool,
+}
+
+impl BrowserSettings {
+    /// launch_command(url)
+    /// What: argv for the configured browser; None means use the OS URL opener
+    /// Calls: core::browser_command()
+    pub fn launch_command(&self, url: &str) -> Option<Vec<String>> {
+        let executable = self.executable.as_deref().map(str::trim).filter(|e| !e.is_empty())?;
+        Some(browser_command(executable, self.profile.as_deref(), self.app_mode, url))
+    }
+}
+
+/// Where a call opens
+/// What: The app's own conference window, or the system browser
+/// Why: Embedded calls support hangup/mute hotkeys and call-state tracking;
+///      the browser works everywhere but the app can't see or control the call
+/// Used by: AppSettings.join_mode, CallDefaults.join_mode, CallController::join()
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinMode {
+    Embedded,
+    #[default]
+    External,
+}
+
+/// Tray icon left-click action
+/// What: Join the primary target, hang up, or open the settings window
+/// Why: One click on the panel is the quickest way in or out of a call
+/// Used by: AppSettings.tray_left_click, Tauri tray icon handler
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayClickAction {
+    JoinPrimary,
+    Hangup,
+    #[default]
+    OpenSettings,
+}
+
 
 
 /// Keyboard shortcut configuration
@@ -93,11 +318,19 @@ pub struct Keybinds {
     #[serde(default)]
     pub target_hotkeys: std::collections::HashMap<String, String>,
     
-    /// In-call shortcuts (future use)
+    /// Per-channel hotkeys (channel id -> hotkey)
+    #[serde(default)]
+    pub channel_hotkeys: std::collections::HashMap<String, String>,
+    
+    /// In-call shortcuts (embedded join mode only)
     #[serde(default)]
     pub toggle_mute: Option<String>,
     #[serde(default)]
     pub toggle_video: Option<String>,
+    
+    /// Switch the conference window to/from the compact always-on-top mini mode
+    #[serde(default)]
+    pub toggle_mini_mode: Option<String>,
 }
 
 impl Default for Keybinds {
@@ -109,8 +342,10 @@ impl Default for Keybinds {
             join_primary: platform_defaults.join_primary,
             hangup: platform_defaults.hangup,
             target_hotkeys: std::collections::HashMap::new(),
+            channel_hotkeys: std::collections::HashMap::new(),
             toggle_mute: None,
             toggle_video: None,
+            toggle_mini_mode: None,
         }
     }
 }
@@ -127,8 +362,10 @@ pub struct Target {
     /// Display name chosen by user
     pub label: String,
     
-    /// Pairing code (high-entropy, shared secret)
-    pub code: String,
+    /// Pairing code (high-entropy, shared secret), or the meeting URL for Link targets
+    /// Secret so Debug/Display of a Target never print it; serializes as a plain string
+    /// (meeting URLs often embed a password, so they get the same protection)
+    pub code: Secret<String>,
     
     /// Target type for UI/behavior differences
     #[serde(rename = "type")]
@@ -141,20 +378,83 @@ pub struct Target {
     /// Per-target call preferences
     pub call_defaults: CallDefaults,
     
-    /// When this target was added (ISO 8601)
-    pub created_at: String,
+    /// When this target was added (RFC 3339)
+    /// Unparseable legacy values still load; SettingsStore reports them
+    pub created_at: Timestamp,
     
     /// Custom notes (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    
+    /// Named sub-rooms sharing this target's code
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<Channel>,
+}
+
+impl Target {
+    /// meeting_url()
+    /// What: URL joining this target opens
+    /// Why: Link targets open their stored URL; everything else derives a room
+    /// Used by: Tauri join path, CLI `join`
+    pub fn meeting_url(&self) -> String {
+        match self.target_type {
+            TargetType::Link => self.code.expose().clone(),
+            TargetType::Person | TargetType::Group => meeting_url(&room_id_from_code(self.code.expose())),
+        }
+    }
+}
+
+/// Named channel (sub-room) of a target
+/// What: A second, third... room derived from the target's code plus the name
+/// Why: Separate "standup" and "pairing" calls with the same partner, no new code
+/// Used by: Target.channels, Keybinds.channel_hotkeys, tray Join menu
+/// Contract:
+///   - Room is core::channel_room_id(code, name); the partner needs the same name
+///   - id is store-generated ("ch_..."); left empty by the UI for new channels
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Channel {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
 }
 
 /// Type of target
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum TargetType {
+    #[default]
     Person,
     Group,
+    /// Fixed meeting URL (Zoom, Meet, a named Jitsi room...) stored in `code`
+    Link,
+}
+
+/// Fields supplied when adding a target
+/// What: A Target without the store-owned id and created_at
+/// Why: The store generates those so the UI and CLI can't produce duplicates
+/// Used by: SettingsStore::create_target(), add_target command, CLI `target add`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NewTarget {
+    pub label: String,
+    
+    /// Pairing code; a fresh one is generated when missing
+    #[serde(default)]
+    pub code: Option<Secret<String>>,
+    
+    #[serde(rename = "type", default)]
+    pub target_type: TargetType,
+    
+    #[serde(default)]
+    pub is_primary: bool,
+    
+    #[serde(default)]
+    pub call_defaults: CallDefaults,
+    
+    #[serde(default)]
+    pub notes: Option<String>,
+    
+    #[serde(default)]
+    pub channels: Vec<Channel>,
 }
 
 /// Per-target call settings
@@ -174,6 +474,38 @@ pub struct CallDefaults {
     /// Use specific display name (overrides global)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+    
+    /// Overrides AppSettings.join_mode for this target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub join_mode: Option<JoinMode>,
+    
+    /// Last conference window size/position for this target
+    /// Written by the app when the window closes (SettingsStore::set_window_geometry)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<WindowGeometry>,
+}
+
+/// Remembered conference window geometry
+/// What: Outer position and inner size in physical pixels, plus the monitor name
+/// Why: Calls reopen where they were; core::place_window() handles monitors
+///      that are gone or have changed
+/// Used by: CallDefaults.window, Tauri ConferenceWindow
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    
+    /// Name of the monitor the window was on (as reported by the OS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+}
+
+impl WindowGeometry {
+    pub fn rect(&self) -> Rect {
+        Rect { x: self.x, y: self.y, width: self.width, height: self.height }
+    }
 }
 
 impl Default for CallDefaults {
@@ -182,6 +514,8 @@ impl Default for CallDefaults {
             start_with_audio: true,
             start_with_video: true,
             display_name: None,
+            join_mode: None,
+            window: None,
         }
     }
 }
@@ -191,6 +525,10 @@ fn default_true() -> bool {
     true
 }
 
+fn default_auto_hangup_after_secs() -> u64 {
+    300
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,12 +549,13 @@ mod tests {
         settings.targets.push(Target {
             id: "tg_123".to_string(),
             label: "Alice".to_string(),
-            code: "test-code-1234".to_string(),
+            code: "test-code-1234".into(),
             target_type: TargetType::Person,
             is_primary: true,
             call_defaults: CallDefaults::default(),
-            created_at: "2024-01-01T00:00:00Z".to_string(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
             notes: Some("Best friend".to_string()),
+            channels: Vec::new(),
         });
         
         // Serialize
@@ -250,6 +589,10 @@ mod tests {
         assert!(settings.app_settings.autostart);
         // New fields should have defaults
         assert!(settings.app_settings.play_join_sound);
+        assert_eq!(settings.app_settings.auto_hangup_after_secs, 300);
+        assert!(!settings.app_settings.prewarm_conference_window);
+        assert_eq!(settings.app_settings.tray_left_click, TrayClickAction::OpenSettings);
+        assert_eq!(settings.app_settings.notifications, NotificationSettings::default());
         assert!(settings.keybinds.toggle_mute.is_none());
     }
     
@@ -275,6 +618,70 @@ mod tests {
         assert_eq!(settings.targets.len(), 0);
     }
     
+    #[test]
+    fn test_meeting_url_by_type() {
+        let mut target: Target = serde_json::from_str(r#"{
+            "id": "1", "label": "Standup", "code": "https://zoom.us/j/123?pwd=x",
+            "type": "link", "call_defaults": {}, "created_at": "2024-01-01T00:00:00Z"
+        }"#).unwrap();
+        assert_eq!(target.target_type, TargetType::Link);
+        assert_eq!(target.meeting_url(), "https://zoom.us/j/123?pwd=x");
+        
+        target.target_type = TargetType::Person;
+        assert_eq!(target.meeting_url(), meeting_url(&room_id_from_code("https://zoom.us/j/123?pwd=x")));
+    }
+    
+    #[test]
+    fn test_browser_launch_command() {
+        let mut browser = BrowserSettings::default();
+        assert_eq!(browser.launch_command("https://meet.jit.si/x"), None);
+        
+        browser.executable = Some("  ".into());
+        assert_eq!(browser.launch_command("https://meet.jit.si/x"), None);
+        
+        browser.executable = Some("chromium".into());
+        browser.app_mode = true;
+        assert_eq!(
+            browser.launch_command("https://meet.jit.si/x"),
+            Some(vec!["chromium".to_string(), "--app=https://meet.jit.si/x".to_string()])
+        );
+    }
+    
+    #[test]
+    fn test_join_mode_for() {
+        let mut settings = Settings::default();
+        let mut target: Target = serde_json::from_str(r#"{
+            "id": "1", "label": "Alice", "code": "abcd", "type": "person",
+            "call_defaults": {}, "created_at": "2024-01-01T00:00:00Z"
+        }"#).unwrap();
+        assert_eq!(settings.join_mode_for(&target), JoinMode::External);
+        
+        settings.app_settings.join_mode = JoinMode::Embedded;
+        assert_eq!(settings.join_mode_for(&target), JoinMode::Embedded);
+        
+        target.call_defaults.join_mode = Some(JoinMode::External);
+        assert_eq!(settings.join_mode_for(&target), JoinMode::External);
+        
+        // Link targets can't be embedded
+        target.call_defaults.join_mode = Some(JoinMode::Embedded);
+        target.target_type = TargetType::Link;
+        assert_eq!(settings.join_mode_for(&target), JoinMode::External);
+    }
+    
+    #[test]
+    fn test_notifies() {
+        let mut app_settings = AppSettings::default();
+        assert!(app_settings.notifies(NotificationKind::PartnerJoined));
+        
+        app_settings.notifications.partner_joined = false;
+        assert!(!app_settings.notifies(NotificationKind::PartnerJoined));
+        assert!(app_settings.notifies(NotificationKind::JoinFailed));
+        
+        // Master switch wins over the per-event toggles
+        app_settings.show_notifications = false;
+        assert!(!app_settings.notifies(NotificationKind::JoinFailed));
+    }
+    
     // Edge case tests
     
     #[test]
@@ -282,12 +689,13 @@ mod tests {
         let target = Target {
             id: "".to_string(), // Empty ID
             label: "".to_string(), // Empty label
-            code: "".to_string(), // Empty code
+            code: "".into(), // Empty code
             target_type: TargetType::Person,
             is_primary: true,
             call_defaults: CallDefaults::default(),
-            created_at: "2024-01-01T00:00:00Z".to_string(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
             notes: Some("".to_string()), // Empty note
+            channels: Vec::new(),
         };
         
         let json = serde_json::to_string(&target).unwrap();
@@ -301,12 +709,13 @@ mod tests {
         settings.targets.push(Target {
             id: "tg_unicode".to_string(),
             label: "张三 & फ्रेंड्स 🎉".to_string(),
-            code: "test-code".to_string(),
+            code: "test-code".into(),
             target_type: TargetType::Group,
             is_primary: false,
             call_defaults: CallDefaults::default(),
-            created_at: "2024-01-01T00:00:00Z".to_string(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
             notes: Some("多语言测试 🌍".to_string()),
+            channels: Vec::new(),
         });
         
         let json = serde_json::to_string(&settings).unwrap();
@@ -323,12 +732,13 @@ mod tests {
             settings.targets.push(Target {
                 id: format!("tg_{}", i),
                 label: format!("Target {}", i),
-                code: format!("code-{}", i),
+                code: format!("code-{}", i).into(),
                 target_type: if i % 2 == 0 { TargetType::Person } else { TargetType::Group },
                 is_primary: i == 0,
                 call_defaults: CallDefaults::default(),
-                created_at: "2024-01-01T00:00:00Z".to_string(),
+                created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
                 notes: if i % 3 == 0 { Some(format!("Note {}", i)) } else { None },
+                channels: Vec::new(),
             });
             
             // Add custom hotkey for first 10
@@ -355,23 +765,25 @@ mod tests {
         settings.targets.push(Target {
             id: "duplicate".to_string(),
             label: "First".to_string(),
-            code: "code1".to_string(),
+            code: "code1".into(),
             target_type: TargetType::Person,
             is_primary: true,
             call_defaults: CallDefaults::default(),
-            created_at: "2024-01-01T00:00:00Z".to_string(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
             notes: None,
+            channels: Vec::new(),
         });
         
         settings.targets.push(Target {
             id: "duplicate".to_string(),
             label: "Second".to_string(),
-            code: "code2".to_string(),
+            code: "code2".into(),
             target_type: TargetType::Person,
             is_primary: false,
             call_defaults: CallDefaults::default(),
-            created_at: "2024-01-01T00:00:00Z".to_string(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
             notes: None,
+            channels: Vec::new(),
         });
         
         // Should serialize both (validation happens elsewhere)
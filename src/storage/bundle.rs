@@ -0,0 +1,645 @@
+// Portable settings bundles (export/import)
+// What: Serializable snapshot of targets (+ optionally settings) and merge logic
+// Why: Moving to a new machine shouldn't mean re-entering every target
+// Used by:
+//   - SettingsStore::export_to_path / import_bundle / preview_import
+//   - Tauri export/import commands for the settings UI
+// Change notes: Bundle files are shared between machines - keep old versions readable
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::file_security;
+use super::settings_store::SettingsStore;
+use crate::core::cipher::{open_with_passphrase, seal_with_passphrase, SealedBox};
+use crate::core::{generate_id, room_id_from_code};
+use crate::models::settings::{AppSettings, Keybinds};
+use crate::models::{Settings, Target};
+
+/// Current bundle schema version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Exported data
+/// What: Targets plus optional app settings and keybinds
+/// Why: Users may want targets only (sharing) or everything (new machine)
+/// Used by: export/import, Tauri commands
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bundle {
+    pub version: u32,
+
+    pub targets: Vec<Target>,
+
+    /// Present when exported with settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_settings: Option<AppSettings>,
+
+    /// Present when exported with settings (includes per-target hotkeys)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keybinds: Option<Keybinds>,
+}
+
+/// On-disk bundle file, plain or passphrase-encrypted
+/// What: Tagged by "format" so import can tell which it got
+/// Why: Encrypted bundles must be recognizable before asking for a passphrase
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "format")]
+pub enum BundleFile {
+    #[serde(rename = "blink-bundle")]
+    Plain(Box<Bundle>),
+    #[serde(rename = "blink-bundle-encrypted")]
+    Encrypted(SealedBox),
+}
+
+/// How to resolve an incoming target that clashes with an existing one
+/// (same id, or a code deriving the same room)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    /// Keep the existing target, drop the incoming one
+    #[default]
+    Skip,
+    /// Replace the existing target's fields (id and primary flag are kept)
+    Overwrite,
+    /// Import alongside under a new id; same-code clashes are skipped since
+    /// both would join the same room
+    Rename,
+}
+
+/// Import options
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ImportOptions {
+    #[serde(default)]
+    pub strategy: MergeStrategy,
+
+    /// Also apply app settings and keybinds from the bundle
+    #[serde(default)]
+    pub import_settings: bool,
+}
+
+/// What happened (or would happen) to one incoming target
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum TargetChange {
+    Add { id: String, label: String },
+    Skip { id: String, label: String, reason: String },
+    Overwrite { id: String, label: String, previous_label: String },
+    Rename { id: String, new_id: String, label: String },
+}
+
+/// Import result / dry-run preview
+/// What: Per-target changes plus whether settings would change
+/// Why: The settings UI shows this diff before the user confirms
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ImportReport {
+    pub changes: Vec<TargetChange>,
+    pub settings_changed: bool,
+}
+
+impl ImportReport {
+    /// Number of targets that end up written (added, overwritten or renamed)
+    pub fn applied_count(&self) -> usize {
+        self.changes.iter()
+            .filter(|c| !matches!(c, TargetChange::Skip { .. }))
+            .count()
+    }
+}
+
+/// build_bundle(settings, include_settings)
+/// What: Snapshots settings into a Bundle
+/// Used by: SettingsStore::export_bundle()
+pub fn build_bundle(settings: &Settings, include_settings: bool) -> Bundle {
+    Bundle {
+        version: BUNDLE_VERSION,
+        targets: settings.targets.clone(),
+        app_settings: include_settings.then(|| settings.app_settings.clone()),
+        keybinds: include_settings.then(|| settings.keybinds.clone()),
+    }
+}
+
+/// write_bundle(path, bundle, passphrase)
+/// What: Writes a bundle as pretty JSON, sealed when a passphrase is given
+/// Used by: SettingsStore::export_to_path()
+pub fn write_bundle(path: &Path, bundle: &Bundle, passphrase: Option<&str>) -> Result<()> {
+    let file = match passphrase {
+        Some(passphrase) => {
+            let plaintext = serde_json::to_vec(bundle).context("Failed to serialize bundle")?;
+            BundleFile::Encrypted(seal_with_passphrase(&plaintext, passphrase)?)
+        }
+        None => BundleFile::Plain(Box::new(bundle.clone())),
+    };
+
+    let json = serde_json::to_string_pretty(&file).context("Failed to serialize bundle")?;
+    // Plain bundles hold codes in the clear - keep them private like settings.json
+    file_security::write_private_file(path, json.as_bytes())
+        .with_context(|| format!("Failed to write bundle to {:?}", path))
+}
+
+/// read_bundle(path, passphrase)
+/// What: Reads a plain or encrypted bundle file
+/// Used by: Tauri import commands, tests
+/// Contract:
+///   - Encrypted bundles without a passphrase fail with a clear message
+///   - Bundles from a newer schema version are rejected
+pub fn read_bundle(path: &Path, passphrase: Option<&str>) -> Result<Bundle> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read bundle from {:?}", path))?;
+    let file: BundleFile = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse bundle from {:?}", path))?;
+
+    let bundle = match file {
+        BundleFile::Plain(bundle) => *bundle,
+        BundleFile::Encrypted(sealed) => {
+            let passphrase = passphrase
+                .context("Bundle is encrypted; a passphrase is required")?;
+            let plaintext = open_with_passphrase(&sealed, passphrase)?;
+            serde_json::from_slice(&plaintext).context("Failed to parse decrypted bundle")?
+        }
+    };
+
+    if bundle.version > BUNDLE_VERSION {
+        bail!("Bundle version {} is newer than supported ({})", bundle.version, BUNDLE_VERSION);
+    }
+
+    Ok(bundle)
+}
+
+/// plan_import(current, bundle, options)
+/// What: Computes the merged settings and a report, without touching disk
+/// Why: Same logic drives the dry-run preview and the real import
+/// Used by: SettingsStore::preview_import(), SettingsStore::import_bundle()
+/// Contract:
+///   - Conflicts are matched by id first, then by derived room
+///   - Every written target passes the same normalization, validation and
+///     code-strength checks as add_target/update_target; failures are Skips
+///   - Renamed targets get new channel ids too (the old ones stay taken)
+///   - Imported targets never steal primary; a primary is assigned if none exists
+///   - App settings never bring their browser or security fields (the
+///     browser is spawned on join; a bundle must not weaken the checks)
+///   - Per-target and per-channel hotkeys follow their targets only when
+///     importing settings, and never replace a key already in use
+pub fn plan_import(current: &Settings, bundle: &Bundle, options: &ImportOptions) -> (Settings, ImportReport) {
+    let mut next = current.clone();
+    let mut report = ImportReport::default();
+    // incoming id -> id it ended up under (targets, then channels)
+    let mut id_map = Vec::new();
+    let mut channel_map = Vec::new();
+
+    for incoming in &bundle.targets {
+        let skip = |reason: String| TargetChange::Skip {
+            id: incoming.id.clone(),
+            label: incoming.label.clone(),
+            reason,
+        };
+
+        let mut target = incoming.clone();
+        SettingsStore::assign_channel_ids(&mut target);
+        if let Err(e) = SettingsStore::normalize_link(&mut target) {
+            report.changes.push(skip(e.to_string()));
+            continue;
+        }
+
+        let incoming_room = room_id_from_code(target.code.expose());
+        let by_id = next.targets.iter().position(|t| t.id == incoming.id);
+        let by_room = next.targets.iter().position(|t| room_id_from_code(t.code.expose()) == incoming_room);
+
+        let Some(existing) = by_id.or(by_room) else {
+            target.is_primary = false;
+            if let Err(e) = check_target(&next, &target, true) {
+                report.changes.push(skip(e.to_string()));
+                continue;
+            }
+            report.changes.push(TargetChange::Add { id: target.id.clone(), label: target.label.clone() });
+            id_map.push((incoming.id.clone(), target.id.clone()));
+            channel_map.extend(target.channels.iter().map(|c| (c.id.clone(), c.id.clone())));
+            next.targets.push(target);
+            continue;
+        };
+
+        let existing_label = next.targets[existing].label.clone();
+
+        match options.strategy {
+            MergeStrategy::Skip => {
+                report.changes.push(skip(format!("conflicts with existing target '{}'", existing_label)));
+            }
+            MergeStrategy::Overwrite => {
+                // Overwriting the id match must not leave a second target on the same room
+                if let (Some(id_index), Some(room_index)) = (by_id, by_room) {
+                    if id_index != room_index {
+                        let other = &next.targets[room_index].label;
+                        report.changes.push(skip(format!("same code as existing target '{}'", other)));
+                        continue;
+                    }
+                }
+
+                let slot = &next.targets[existing];
+                target.id = slot.id.clone();
+                target.is_primary = slot.is_primary;
+                target.created_at = slot.created_at.clone();
+                let code_changed = target.code != slot.code;
+                if let Err(e) = check_target(&next, &target, code_changed) {
+                    report.changes.push(skip(e.to_string()));
+                    continue;
+                }
+
+                report.changes.push(TargetChange::Overwrite {
+                    id: target.id.clone(),
+                    label: target.label.clone(),
+                    previous_label: existing_label,
+                });
+                id_map.push((incoming.id.clone(), target.id.clone()));
+                channel_map.extend(target.channels.iter().map(|c| (c.id.clone(), c.id.clone())));
+                next.targets[existing] = target;
+            }
+            MergeStrategy::Rename => {
+                if by_room.is_some() {
+                    report.changes.push(skip(format!("same code as existing target '{}'", existing_label)));
+                    continue;
+                }
+
+                target.id = unique_id(&next, &incoming.id);
+                target.label = format!("{} (imported)", incoming.label);
+                target.is_primary = false;
+                let mut renamed_channels = Vec::new();
+                for channel in &mut target.channels {
+                    let new_id = generate_id("ch");
+                    renamed_channels.push((std::mem::replace(&mut channel.id, new_id), channel.id.clone()));
+                }
+                if let Err(e) = check_target(&next, &target, true) {
+                    report.changes.push(skip(e.to_string()));
+                    continue;
+                }
+
+                report.changes.push(TargetChange::Rename {
+                    id: incoming.id.clone(),
+                    new_id: target.id.clone(),
+                    label: target.label.clone(),
+                });
+                id_map.push((incoming.id.clone(), target.id.clone()));
+                channel_map.extend(renamed_channels);
+                next.targets.push(target);
+            }
+        }
+    }
+
+    if !next.targets.is_empty() && !next.targets.iter().any(|t| t.is_primary) {
+        next.targets[0].is_primary = true;
+    }
+
+    if options.import_settings {
+        if let Some(app_settings) = &bundle.app_settings {
+            next.app_settings = merge_app_settings(&current.app_settings, app_settings);
+        }
+        if let Some(keybinds) = &bundle.keybinds {
+            merge_keybinds(&mut next.keybinds, keybinds, &id_map, &channel_map);
+        }
+    }
+
+    report.settings_changed = next.app_settings != current.app_settings
+        || next.keybinds != current.keybinds;
+
+    (next, report)
+}
+
+/// The store's add/update checks against the planned settings
+fn check_target(planned: &Settings, target: &Target, check_strength: bool) -> Result<()> {
+    SettingsStore::validate_target(planned, target)?;
+    if check_strength {
+        SettingsStore::check_code_strength(planned, target)?;
+    }
+    Ok(())
+}
+
+/// Bundle preferences, except what only this machine should decide: the
+/// browser executable/profile and the security settings
+fn merge_app_settings(current: &AppSettings, incoming: &AppSettings) -> AppSettings {
+    AppSettings {
+        browser: current.browser.clone(),
+        strict_permissions: current.strict_permissions,
+        min_code_strength: current.min_code_strength,
+        ..incoming.clone()
+    }
+}
+
+/// Take global hotkeys from the bundle; carry per-target and per-channel
+/// hotkeys over for what was imported, skipping keys already bound to
+/// something else
+fn merge_keybinds(
+    current: &mut Keybinds,
+    incoming: &Keybinds,
+    id_map: &[(String, String)],
+    channel_map: &[(String, String)],
+) {
+    current.join_primary = incoming.join_primary.clone();
+    current.hangup = incoming.hangup.clone();
+    current.toggle_mute = incoming.toggle_mute.clone();
+    current.toggle_video = incoming.toggle_video.clone();
+    current.toggle_mini_mode = incoming.toggle_mini_mode.clone();
+
+    for (incoming_id, final_id) in id_map {
+        let Some(key) = incoming.target_hotkeys.get(incoming_id) else { continue };
+        if !key_in_use(current, key, final_id) {
+            current.target_hotkeys.insert(final_id.clone(), key.clone());
+        }
+    }
+    for (incoming_id, final_id) in channel_map {
+        let Some(key) = incoming.channel_hotkeys.get(incoming_id) else { continue };
+        if !key_in_use(current, key, final_id) {
+            current.channel_hotkeys.insert(final_id.clone(), key.clone());
+        }
+    }
+}
+
+/// Whether `key` is bound to anything but the target/channel `owner`
+fn key_in_use(keybinds: &Keybinds, key: &String, owner: &str) -> bool {
+    [&keybinds.join_primary, &keybinds.hangup].contains(&key)
+        || keybinds.toggle_mute.as_ref() == Some(key)
+        || keybinds.toggle_video.as_ref() == Some(key)
+        || keybinds.toggle_mini_mode.as_ref() == Some(key)
+        || keybinds.target_hotkeys.iter().any(|(id, k)| k == key && id != owner)
+        || keybinds.channel_hotkeys.iter().any(|(id, k)| k == key && id != owner)
+}
+
+/// "<id>_2", "<id>_3", ... - first one not taken
+fn unique_id(settings: &Settings, base: &str) -> String {
+    (2..)
+        .map(|n| format!("{}_{}", base, n))
+        .find(|candidate| !settings.targets.iter().any(|t| &t.id == candidate))
+        .expect("unbounded range always yields an id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{generate_code_base32_100b, CodeStrength};
+    use crate::models::{CallDefaults, Channel, TargetType};
+    use tempfile::TempDir;
+
+    fn target(id: &str, code: &str) -> Target {
+        Target {
+            id: id.to_string(),
+            label: format!("Label {}", id),
+            code: code.into(),
+            target_type: TargetType::Person,
+            is_primary: false,
+            call_defaults: CallDefaults::default(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            notes: None,
+            channels: Vec::new(),
+        }
+    }
+
+    /// Merge-logic fixtures use short codes; test_imported_targets_are_validated
+    /// covers the strength check
+    fn settings_with(targets: Vec<Target>) -> Settings {
+        let mut settings = Settings { targets, ..Settings::default() };
+        settings.app_settings.min_code_strength = CodeStrength::Weak;
+        settings
+    }
+
+    fn bundle_of(targets: Vec<Target>) -> Bundle {
+        build_bundle(&settings_with(targets), false)
+    }
+
+    fn options(strategy: MergeStrategy) -> ImportOptions {
+        ImportOptions { strategy, import_settings: false }
+    }
+
+    #[test]
+    fn test_plain_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bundle.json");
+        let bundle = bundle_of(vec![target("a", "code-a")]);
+
+        write_bundle(&path, &bundle, None).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\"format\": \"blink-bundle\""));
+
+        assert_eq!(read_bundle(&path, None).unwrap(), bundle);
+    }
+
+    #[test]
+    fn test_encrypted_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bundle.json");
+        let bundle = bundle_of(vec![target("a", "super-secret-code")]);
+
+        write_bundle(&path, &bundle, Some("hunter2")).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("blink-bundle-encrypted"));
+        assert!(!contents.contains("super-secret-code"));
+
+        assert_eq!(read_bundle(&path, Some("hunter2")).unwrap(), bundle);
+        assert!(read_bundle(&path, Some("wrong")).is_err());
+
+        let err = read_bundle(&path, None).unwrap_err().to_string();
+        assert!(err.contains("passphrase is required"), "Error was: {}", err);
+    }
+
+    #[test]
+    fn test_import_adds_new_targets() {
+        let current = settings_with(vec![target("a", "code-a")]);
+        let bundle = bundle_of(vec![target("b", "code-b")]);
+
+        let (next, report) = plan_import(&current, &bundle, &options(MergeStrategy::Skip));
+        assert_eq!(next.targets.len(), 2);
+        assert_eq!(report.changes, vec![TargetChange::Add { id: "b".into(), label: "Label b".into() }]);
+        assert!(!report.settings_changed);
+    }
+
+    #[test]
+    fn test_skip_strategy() {
+        let mut existing = target("a", "code-a");
+        existing.is_primary = true;
+        let current = settings_with(vec![existing]);
+        // Same id, and a different id with a hyphen-variant of the same code
+        let bundle = bundle_of(vec![target("a", "other"), target("x", "codea")]);
+
+        let (next, report) = plan_import(&current, &bundle, &options(MergeStrategy::Skip));
+        assert_eq!(next, current);
+        assert_eq!(report.applied_count(), 0);
+        assert_eq!(report.changes.len(), 2);
+    }
+
+    #[test]
+    fn test_overwrite_strategy_keeps_id_and_primary() {
+        let mut existing = target("a", "code-a");
+        existing.is_primary = true;
+        let current = settings_with(vec![existing]);
+
+        let mut incoming = target("x", "code-a");
+        incoming.label = "New label".to_string();
+        let bundle = bundle_of(vec![incoming]);
+
+        let (next, report) = plan_import(&current, &bundle, &options(MergeStrategy::Overwrite));
+        assert_eq!(next.targets.len(), 1);
+        assert_eq!(next.targets[0].id, "a");
+        assert_eq!(next.targets[0].label, "New label");
+        assert!(next.targets[0].is_primary);
+        assert!(matches!(report.changes[0], TargetChange::Overwrite { .. }));
+    }
+
+    #[test]
+    fn test_rename_strategy() {
+        let current = settings_with(vec![target("a", "code-a"), target("a_2", "code-a2")]);
+        let bundle = bundle_of(vec![target("a", "code-new"), target("y", "code-a")]);
+
+        let (next, report) = plan_import(&current, &bundle, &options(MergeStrategy::Rename));
+        assert_eq!(next.targets.len(), 3);
+        assert_eq!(next.targets[2].id, "a_3");
+        assert_eq!(next.targets[2].label, "Label a (imported)");
+
+        // Same-room clash can't be renamed away
+        assert!(matches!(report.changes[1], TargetChange::Skip { .. }));
+    }
+
+    #[test]
+    fn test_import_settings_and_hotkeys() {
+        let current = settings_with(vec![target("a", "code-a")]);
+
+        let mut source = settings_with(vec![target("a", "code-x"), target("b", "code-b")]);
+        source.app_settings.autostart = true;
+        source.keybinds.target_hotkeys.insert("a".into(), "Ctrl+Alt+1".into());
+        source.keybinds.target_hotkeys.insert("b".into(), "Ctrl+Alt+2".into());
+        let bundle = build_bundle(&source, true);
+
+        let options = ImportOptions { strategy: MergeStrategy::Rename, import_settings: true };
+        let (next, report) = plan_import(&current, &bundle, &options);
+
+        assert!(report.settings_changed);
+        assert!(next.app_settings.autostart);
+        // Renamed target's hotkey follows it to the new id
+        assert_eq!(next.keybinds.target_hotkeys.get("a_2").map(String::as_str), Some("Ctrl+Alt+1"));
+        assert_eq!(next.keybinds.target_hotkeys.get("b").map(String::as_str), Some("Ctrl+Alt+2"));
+        assert!(!next.keybinds.target_hotkeys.contains_key("a"));
+    }
+
+    #[test]
+    fn test_channel_hotkeys_follow_import() {
+        let current = settings_with(vec![target("a", "code-a")]);
+
+        let mut with_channel = target("b", "code-b");
+        with_channel.channels.push(Channel { id: "ch_standup".into(), name: "Standup".into() });
+        let mut clash = target("a", "code-other");
+        clash.channels.push(Channel { id: "ch_retro".into(), name: "Retro".into() });
+        let mut source = settings_with(vec![with_channel, clash]);
+        source.keybinds.channel_hotkeys.insert("ch_standup".into(), "Ctrl+Alt+3".into());
+        source.keybinds.channel_hotkeys.insert("ch_retro".into(), "Ctrl+Alt+4".into());
+        let bundle = build_bundle(&source, true);
+
+        let options = ImportOptions { strategy: MergeStrategy::Rename, import_settings: true };
+        let (next, _) = plan_import(&current, &bundle, &options);
+
+        let hotkeys = &next.keybinds.channel_hotkeys;
+        assert_eq!(hotkeys.get("ch_standup").map(String::as_str), Some("Ctrl+Alt+3"));
+        // The renamed target's channel got a new id; its hotkey follows it
+        let renamed = next.targets.iter().find(|t| t.id == "a_2").unwrap();
+        let retro_id = &renamed.channels[0].id;
+        assert_ne!(retro_id, "ch_retro");
+        assert_eq!(hotkeys.get(retro_id).map(String::as_str), Some("Ctrl+Alt+4"));
+        assert!(!hotkeys.contains_key("ch_retro"));
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_imported_targets_are_validated() {
+        // Default settings: codes must be at least Ok strength
+        let current = Settings::default();
+        let strong = generate_code_base32_100b();
+
+        let mut link = target("link", "zoom.us/j/123");
+        link.target_type = TargetType::Link;
+        let mut script = target("script", "javascript:alert(1)");
+        script.target_type = TargetType::Link;
+        let mut file = target("file", "file:///etc/passwd");
+        file.target_type = TargetType::Link;
+        let mut link_with_channel = target("link2", "https://zoom.us/j/456");
+        link_with_channel.target_type = TargetType::Link;
+        link_with_channel.channels.push(Channel { id: "ch_x".into(), name: "x".into() });
+        let mut duplicate_channels = target("dup", &format!("{}-dup", strong));
+        duplicate_channels.channels = vec![
+            Channel { id: "ch_same".into(), name: "One".into() },
+            Channel { id: "ch_same".into(), name: "Two".into() },
+        ];
+        let bundle = bundle_of(vec![
+            target("weak", "abc"),
+            target("empty", ""),
+            target("x'); alert(1); ('", &strong),
+            script,
+            file,
+            link_with_channel,
+            duplicate_channels,
+            link,
+        ]);
+
+        let (next, report) = plan_import(&current, &bundle, &options(MergeStrategy::Skip));
+        assert_eq!(report.applied_count(), 1, "{:?}", report.changes);
+        assert_eq!(report.changes.iter().filter(|c| matches!(c, TargetChange::Skip { .. })).count(), 7);
+        assert_eq!(next.targets.len(), 1);
+        assert_eq!(next.targets[0].code.expose(), "https://zoom.us/j/123");
+    }
+
+    #[test]
+    fn test_import_keeps_local_browser_and_security() {
+        let mut current = settings_with(Vec::new());
+        current.app_settings.strict_permissions = true;
+        current.app_settings.min_code_strength = CodeStrength::Strong;
+
+        let mut source = settings_with(Vec::new());
+        source.app_settings.autostart = true;
+        source.app_settings.browser.executable = Some("/tmp/payload".into());
+        source.app_settings.strict_permissions = false;
+        let bundle = build_bundle(&source, true);
+
+        let options = ImportOptions { strategy: MergeStrategy::Skip, import_settings: true };
+        let (next, report) = plan_import(&current, &bundle, &options);
+        assert!(report.settings_changed);
+        assert!(next.app_settings.autostart);
+        assert_eq!(next.app_settings.browser, current.app_settings.browser);
+        assert!(next.app_settings.strict_permissions);
+        assert_eq!(next.app_settings.min_code_strength, CodeStrength::Strong);
+    }
+
+    #[test]
+    fn test_primary_assigned_when_importing_into_empty() {
+        let mut incoming = target("a", "code-a");
+        incoming.is_primary = true;
+        let bundle = bundle_of(vec![target("b", "code-b"), incoming]);
+
+        let (next, _) = plan_import(&settings_with(Vec::new()), &bundle, &options(MergeStrategy::Skip));
+        assert_eq!(next.targets.iter().filter(|t| t.is_primary).count(), 1);
+        assert!(next.targets[0].is_primary);
+    }
+
+    #[test]
+    fn test_hotkey_already_in_use_not_imported() {
+        let mut current = settings_with(vec![target("a", "code-a")]);
+        current.keybinds.target_hotkeys.insert("a".into(), "Ctrl+Alt+1".into());
+
+        let mut source = settings_with(vec![target("b", "code-b")]);
+        source.keybinds = current.keybinds.clone();
+        source.keybinds.target_hotkeys.clear();
+        source.keybinds.target_hotkeys.insert("b".into(), "Ctrl+Alt+1".into());
+        let bundle = build_bundle(&source, true);
+
+        let options = ImportOptions { strategy: MergeStrategy::Skip, import_settings: true };
+        let (next, _) = plan_import(&current, &bundle, &options);
+        assert!(!next.keybinds.target_hotkeys.contains_key("b"));
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bundle.json");
+
+        fs::write(&path, r#"{"version": 1, "targets": []}"#).unwrap();
+        assert!(read_bundle(&path, None).is_err(), "Missing format tag");
+
+        fs::write(&path, r#"{"format": "blink-bundle", "version": 99, "targets": []}"#).unwrap();
+        assert!(read_bundle(&path, None).is_err(), "Future version");
+
+        assert!(read_bundle(&temp_dir.path().join("missing.json"), None).is_err());
+    }
+}
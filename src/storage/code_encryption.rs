@@ -0,0 +1,290 @@
+// Pairing-code encryption at rest
+// What: Encrypts Target.code values inside settings.json
+// Why: settings.json otherwise lets anyone who reads it join every private room
+// Used by:
+//   - SettingsStore load/save/enable_code_encryption
+// Calls: core::cipher
+// Change notes: ENCRYPTED_PREFIX marks the on-disk format - bump the version tag if it changes
+
+use anyhow::{bail, Context, Result};
+use data_encoding::BASE64;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file_security;
+use crate::core::cipher::{self, KEY_LEN};
+use crate::models::{CodeEncryption, KeySource, Settings};
+
+/// Prefix of an encrypted code in settings.json
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Known plaintext sealed into CodeEncryption.verifier
+const VERIFIER_PLAINTEXT: &[u8] = b"blink-code-encryption";
+
+/// Raw key protecting the codes
+pub type CodeKey = [u8; KEY_LEN];
+
+/// Error returned when settings need a passphrase that wasn't given
+/// What: Distinguishable via anyhow downcast
+/// Why: The app starts locked and asks for the passphrase instead of failing
+/// Used by: SettingsStore::load_from_path(), Tauri setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingsLocked;
+
+impl fmt::Display for SettingsLocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Settings are protected by a passphrase")
+    }
+}
+
+impl std::error::Error for SettingsLocked {}
+
+/// keyfile_path(settings_path)
+/// What: Keyfile lives next to the settings file ("settings.json" -> "settings.key")
+/// Used by: setup(), unlock()
+pub fn keyfile_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_extension("key")
+}
+
+/// is_encrypted(code)
+/// What: True if a stored code is in the encrypted format
+pub fn is_encrypted(code: &str) -> bool {
+    code.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// setup(method, passphrase, settings_path)
+/// What: Creates a key for the chosen method and the matching CodeEncryption record
+/// Why: First step of enabling encryption / migrating a plaintext file
+/// Used by: SettingsStore::enable_code_encryption()
+/// Contract:
+///   - Keyfile: reuses an existing keyfile, otherwise creates one (0600 on Unix)
+///   - Passphrase: required and non-empty; fresh salt every call
+pub fn setup(method: KeySource, passphrase: Option<&str>, settings_path: &Path) -> Result<(CodeEncryption, CodeKey)> {
+    let (key, salt) = match method {
+        KeySource::Keyfile => {
+            let path = keyfile_path(settings_path);
+            let key = if path.exists() { read_keyfile(&path)? } else { create_keyfile(&path)? };
+            (key, None)
+        }
+        KeySource::Passphrase => {
+            let passphrase = passphrase
+                .filter(|p| !p.is_empty())
+                .context("A passphrase is required")?;
+            let salt = cipher::generate_salt();
+            (cipher::derive_key(passphrase, &salt)?, Some(BASE64.encode(&salt)))
+        }
+    };
+
+    let verifier = cipher::seal_with_key(&key, VERIFIER_PLAINTEXT)?;
+    Ok((CodeEncryption { method, salt, verifier }, key))
+}
+
+/// unlock(info, settings_path, passphrase)
+/// What: Recovers the key for an encrypted settings file and checks it
+/// Used by: SettingsStore load
+/// Contract:
+///   - Passphrase method without a passphrase returns SettingsLocked
+///   - Wrong passphrase / wrong keyfile is an error (verifier mismatch)
+pub fn unlock(info: &CodeEncryption, settings_path: &Path, passphrase: Option<&str>) -> Result<CodeKey> {
+    let key = match info.method {
+        KeySource::Keyfile => read_keyfile(&keyfile_path(settings_path))?,
+        KeySource::Passphrase => {
+            let Some(passphrase) = passphrase else {
+                return Err(SettingsLocked.into());
+            };
+            let salt = info.salt.as_deref().context("Encrypted settings are missing the salt")?;
+            let salt = BASE64.decode(salt.as_bytes()).context("Invalid salt in settings")?;
+            cipher::derive_key(passphrase, &salt)?
+        }
+    };
+
+    match cipher::open_with_key(&key, &info.verifier) {
+        Ok(plaintext) if plaintext == VERIFIER_PLAINTEXT => Ok(key),
+        _ => match info.method {
+            KeySource::Keyfile => bail!("Keyfile does not match the encrypted settings"),
+            KeySource::Passphrase => bail!("Wrong passphrase"),
+        },
+    }
+}
+
+/// encrypt_codes(settings, key)
+/// What: Replaces every Target.code with its encrypted form
+/// Used by: SettingsStore::save() (on a copy, never the in-memory settings)
+/// Contract: Expects plaintext codes - a code that merely starts with
+///           ENCRYPTED_PREFIX is sealed like any other, so it loads back as typed
+pub fn encrypt_codes(settings: &mut Settings, key: &CodeKey) -> Result<()> {
+    for target in &mut settings.targets {
+        let sealed = cipher::seal_with_key(key, target.code.expose().as_bytes())?;
+        target.code = format!("{}{}", ENCRYPTED_PREFIX, sealed).into();
+    }
+    Ok(())
+}
+
+/// decrypt_codes(settings, key)
+/// What: Replaces encrypted codes with plaintext; returns how many were still plaintext
+/// Why: The count tells load() a migration save is needed
+/// Used by: SettingsStore load
+pub fn decrypt_codes(settings: &mut Settings, key: &CodeKey) -> Result<usize> {
+    let mut plaintext_count = 0;
+    for target in &mut settings.targets {
+        match target.code.expose().strip_prefix(ENCRYPTED_PREFIX) {
+            Some(sealed) => {
+                let bytes = cipher::open_with_key(key, sealed)
+                    .with_context(|| format!("Failed to decrypt code for target {}", target.id))?;
+                target.code = String::from_utf8(bytes)
+                    .with_context(|| format!("Decrypted code for target {} is not UTF-8", target.id))?
+                    .into();
+            }
+            None => plaintext_count += 1,
+        }
+    }
+    Ok(plaintext_count)
+}
+
+fn read_keyfile(path: &Path) -> Result<CodeKey> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read keyfile {:?}", path))?;
+    let bytes = BASE64.decode(contents.trim().as_bytes())
+        .with_context(|| format!("Invalid keyfile {:?}", path))?;
+    bytes.try_into()
+        .map_err(|_| anyhow::anyhow!("Keyfile {:?} has the wrong length", path))
+}
+
+fn create_keyfile(path: &Path) -> Result<CodeKey> {
+    if let Some(parent) = path.parent() {
+        file_security::create_private_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {:?}", parent))?;
+    }
+
+    let key = cipher::generate_key();
+    file_security::write_private_file(path, BASE64.encode(&key).as_bytes())
+        .with_context(|| format!("Failed to write keyfile {:?}", path))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CallDefaults, Target, TargetType};
+    use tempfile::TempDir;
+
+    fn settings_with_code(code: &str) -> Settings {
+        let mut settings = Settings::default();
+        settings.targets.push(Target {
+            id: "tg_1".to_string(),
+            label: "Alice".to_string(),
+            code: code.into(),
+            target_type: TargetType::Person,
+            is_primary: true,
+            call_defaults: CallDefaults::default(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            notes: None,
+            channels: Vec::new(),
+        });
+        settings
+    }
+
+    #[test]
+    fn test_keyfile_setup_and_unlock() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+
+        let (info, key) = setup(KeySource::Keyfile, None, &settings_path).unwrap();
+        assert_eq!(info.method, KeySource::Keyfile);
+        assert!(info.salt.is_none());
+        assert!(temp_dir.path().join("settings.key").exists());
+
+        assert_eq!(unlock(&info, &settings_path, None).unwrap(), key);
+
+        // Second setup reuses the keyfile
+        let (_, again) = setup(KeySource::Keyfile, None, &settings_path).unwrap();
+        assert_eq!(again, key);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_keyfile_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        setup(KeySource::Keyfile, None, &settings_path).unwrap();
+
+        let mode = fs::metadata(keyfile_path(&settings_path)).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_passphrase_setup_and_unlock() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+
+        let (info, key) = setup(KeySource::Passphrase, Some("open sesame"), &settings_path).unwrap();
+        assert!(info.salt.is_some());
+        assert!(!keyfile_path(&settings_path).exists());
+
+        assert_eq!(unlock(&info, &settings_path, Some("open sesame")).unwrap(), key);
+        assert!(unlock(&info, &settings_path, Some("wrong")).is_err());
+
+        let locked = unlock(&info, &settings_path, None).unwrap_err();
+        assert!(locked.downcast_ref::<SettingsLocked>().is_some());
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        let key = cipher::generate_key();
+        let mut settings = settings_with_code("abcd-efgh-ijkl");
+
+        encrypt_codes(&mut settings, &key).unwrap();
+        assert!(is_encrypted(settings.targets[0].code.expose()));
+        assert!(!settings.targets[0].code.expose().contains("abcd"));
+
+        assert_eq!(decrypt_codes(&mut settings, &key).unwrap(), 0);
+        assert_eq!(settings.targets[0].code.expose(), "abcd-efgh-ijkl");
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_prefixed_plaintext_code_sealed() {
+        let key = cipher::generate_key();
+        let code = format!("{}our-room", ENCRYPTED_PREFIX);
+        let mut settings = settings_with_code(&code);
+
+        encrypt_codes(&mut settings, &key).unwrap();
+        assert_ne!(settings.targets[0].code.expose(), &code);
+        assert_eq!(decrypt_codes(&mut settings, &key).unwrap(), 0);
+        assert_eq!(settings.targets[0].code.expose(), &code);
+    }
+
+    #[test]
+    fn test_plaintext_codes_counted() {
+        let key = cipher::generate_key();
+        let mut settings = settings_with_code("plain-code");
+        assert_eq!(decrypt_codes(&mut settings, &key).unwrap(), 1);
+        assert_eq!(settings.targets[0].code.expose(), "plain-code");
+    }
+
+    #[test]
+    fn test_wrong_or_missing_keyfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        let (info, _) = setup(KeySource::Keyfile, None, &settings_path).unwrap();
+
+        fs::remove_file(keyfile_path(&settings_path)).unwrap();
+        assert!(unlock(&info, &settings_path, None).is_err());
+
+        setup(KeySource::Keyfile, None, &settings_path).unwrap(); // New, different key
+        let err = unlock(&info, &settings_path, None).unwrap_err().to_string();
+        assert!(err.contains("does not match"), "Error was: {}", err);
+    }
+
+    #[test]
+    fn test_empty_passphrase_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        assert!(setup(KeySource::Passphrase, None, &settings_path).is_err());
+        assert!(setup(KeySource::Passphrase, Some(""), &settings_path).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+// Target conflict detection and merging
+// What: Finds targets that would join the same room and merges them
+// Why: room_id_from_code() strips hyphens and truncates the hash, so two
+//      different-looking codes can silently put both targets in one room
+// Used by:
+//   - SettingsStore add/update (reject), load (warn), merge_targets()
+//   - Tauri target commands (AppError::TargetConflict)
+// Calls: core::room_id_from_code
+// Change notes: If room derivation changes, conflicts must still compare derived rooms;
+//               Link targets compare their URLs (see join_key)
+
+use serde::Serialize;
+use std::fmt;
+
+use crate::core::{normalize_channel_name, room_id_from_code};
+use crate::models::{Channel, Target, TargetType};
+
+/// Why two targets conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Identical pairing codes
+    SameCode,
+    /// Different codes that derive the same room (e.g. differ only by hyphens)
+    SameRoom,
+}
+
+/// Structured conflict between a target and one already stored
+/// What: Returned (via anyhow, downcastable) from add/update, listed on load
+/// Why: The UI offers "merge" instead of just showing an error string
+/// Used by: SettingsStore, Tauri AppError::TargetConflict
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TargetConflict {
+    pub kind: ConflictKind,
+    /// Target being added/updated (or the later one in the list on load)
+    pub target_id: String,
+    pub target_label: String,
+    /// Target it collides with
+    pub existing_id: String,
+    pub existing_label: String,
+}
+
+impl fmt::Display for TargetConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ConflictKind::SameCode => write!(
+                f,
+                "Target '{}' already uses this pairing code",
+                self.existing_label
+            ),
+            ConflictKind::SameRoom => write!(
+                f,
+                "Target '{}' already joins the same room with a near-identical code",
+                self.existing_label
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TargetConflict {}
+
+/// find_conflict(targets, candidate)
+/// What: First stored target (other than the candidate itself) sharing its room
+/// Used by: SettingsStore::add_target(), update_target()
+pub fn find_conflict(targets: &[Target], candidate: &Target) -> Option<TargetConflict> {
+    let key = join_key(candidate);
+    targets.iter()
+        .filter(|t| t.id != candidate.id)
+        .find(|t| join_key(t) == key)
+        .map(|existing| conflict_between(candidate, existing))
+}
+
+/// find_all_conflicts(targets)
+/// What: Every pair of targets sharing a room, later target vs earlier one
+/// Used by: SettingsStore load warnings, Tauri get_target_conflicts
+pub fn find_all_conflicts(targets: &[Target]) -> Vec<TargetConflict> {
+    let keys: Vec<String> = targets.iter().map(join_key).collect();
+
+    let mut conflicts = Vec::new();
+    for (i, target) in targets.iter().enumerate() {
+        if let Some(j) = (0..i).find(|&j| keys[j] == keys[i]) {
+            conflicts.push(conflict_between(target, &targets[j]));
+        }
+    }
+    conflicts
+}
+
+/// What ends up opened: the derived room, or the URL itself for Link targets
+/// (hyphen-stripping would make unrelated URLs look alike)
+fn join_key(target: &Target) -> String {
+    match target.target_type {
+        TargetType::Link => format!("link:{}", target.code.expose()),
+        TargetType::Person | TargetType::Group => room_id_from_code(target.code.expose()),
+    }
+}
+
+fn conflict_between(target: &Target, existing: &Target) -> TargetConflict {
+    let kind = if target.code == existing.code {
+        ConflictKind::SameCode
+    } else {
+        ConflictKind::SameRoom
+    };
+    TargetConflict {
+        kind,
+        target_id: target.id.clone(),
+        target_label: target.label.clone(),
+        existing_id: existing.id.clone(),
+        existing_label: existing.label.clone(),
+    }
+}
+
+/// duplicate_channels(keep, other)
+/// What: Pairs (other's channel, keep's channel) with the same normalized name
+/// Used by: merge_into(), SettingsStore::merge_targets() (moves channel hotkeys)
+pub fn duplicate_channels<'a>(keep: &'a Target, other: &'a Target) -> Vec<(&'a Channel, &'a Channel)> {
+    other.channels.iter()
+        .filter_map(|theirs| {
+            let name = normalize_channel_name(&theirs.name);
+            keep.channels.iter()
+                .find(|mine| normalize_channel_name(&mine.name) == name)
+                .map(|mine| (theirs, mine))
+        })
+        .collect()
+}
+
+/// merge_into(keep, other)
+/// What: Folds other's label, notes, channels and primary flag into keep
+/// Why: Duplicates usually come from adding the same person twice under two names
+/// Used by: SettingsStore::merge_targets()
+/// Contract:
+///   - Labels: "Keep / Other" unless equal (case-insensitive)
+///   - Notes: both, separated by a blank line, duplicates dropped
+///   - Channels: other's are appended unless keep has one with the same name
+///   - keep becomes primary if either was; keep's code and call defaults win
+pub fn merge_into(keep: &mut Target, other: &Target) {
+    if !keep.label.eq_ignore_ascii_case(&other.label) {
+        keep.label = format!("{} / {}", keep.label, other.label);
+    }
+
+    keep.notes = match (keep.notes.take(), other.notes.as_deref()) {
+        (Some(mine), Some(theirs)) if mine.trim() != theirs.trim() => Some(format!("{}\n\n{}", mine, theirs)),
+        (Some(mine), _) => Some(mine),
+        (None, theirs) => theirs.map(String::from),
+    };
+
+    let duplicates: Vec<String> = duplicate_channels(keep, other).iter()
+        .map(|(theirs, _)| theirs.id.clone())
+        .collect();
+    keep.channels.extend(other.channels.iter().filter(|c| !duplicates.contains(&c.id)).cloned());
+
+    keep.is_primary |= other.is_primary;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CallDefaults;
+
+    fn target(id: &str, label: &str, code: &str) -> Target {
+        Target {
+            id: id.to_string(),
+            label: label.to_string(),
+            code: code.into(),
+            target_type: TargetType::Person,
+            is_primary: false,
+            call_defaults: CallDefaults::default(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            notes: None,
+            channels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_same_code_and_same_room() {
+        let targets = vec![target("a", "Alice", "abcd-efgh-ijkl")];
+
+        let conflict = find_conflict(&targets, &target("b", "Al", "abcd-efgh-ijkl")).unwrap();
+        assert_eq!(conflict.kind, ConflictKind::SameCode);
+        assert_eq!(conflict.existing_id, "a");
+
+        // Hyphens are stripped before deriving the room
+        let conflict = find_conflict(&targets, &target("b", "Al", "abcdefgh-ijkl")).unwrap();
+        assert_eq!(conflict.kind, ConflictKind::SameRoom);
+
+        assert!(find_conflict(&targets, &target("b", "Bob", "zzzz-zzzz-zzzz")).is_none());
+        // A target never conflicts with itself (updates)
+        assert!(find_conflict(&targets, &target("a", "Alice", "abcd-efgh-ijkl")).is_none());
+    }
+
+    #[test]
+    fn test_find_all_conflicts() {
+        let targets = vec![
+            target("a", "Alice", "abcd-efgh"),
+            target("b", "Bob", "bbbb-bbbb"),
+            target("c", "Alice 2", "abcdefgh"),
+            target("d", "Alice 3", "abcd-efgh"),
+        ];
+
+        let conflicts = find_all_conflicts(&targets);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!((conflicts[0].target_id.as_str(), conflicts[0].existing_id.as_str()), ("c", "a"));
+        assert_eq!((conflicts[1].target_id.as_str(), conflicts[1].kind), ("d", ConflictKind::SameCode));
+    }
+
+    #[test]
+    fn test_merge_into() {
+        let mut keep = target("a", "Alice", "abcd");
+        keep.notes = Some("Work laptop".into());
+        keep.channels = vec![Channel { id: "ch_1".into(), name: "Standup".into() }];
+        let mut other = target("b", "Alice (home)", "abcd");
+        other.notes = Some("Home".into());
+        other.is_primary = true;
+        other.channels = vec![
+            Channel { id: "ch_2".into(), name: "standup".into() },
+            Channel { id: "ch_3".into(), name: "Pairing".into() },
+        ];
+
+        merge_into(&mut keep, &other);
+        let channel_ids: Vec<&str> = keep.channels.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(channel_ids, ["ch_1", "ch_3"]);
+        assert_eq!(keep.label, "Alice / Alice (home)");
+        assert_eq!(keep.notes.as_deref(), Some("Work laptop\n\nHome"));
+        assert!(keep.is_primary);
+        assert_eq!(keep.id, "a");
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_merge_same_label_and_notes() {
+        let mut keep = target("a", "Alice", "abcd");
+        let mut other = target("b", "alice", "abcd");
+        other.notes = Some("Note".into());
+
+        merge_into(&mut keep, &other);
+        assert_eq!(keep.label, "Alice");
+        assert_eq!(keep.notes.as_deref(), Some("Note"));
+
+        merge_into(&mut keep, &other);
+        assert_eq!(keep.notes.as_deref(), Some("Note"));
+    }
+
+    #[test]
+    fn test_links_compare_whole_urls() {
+        let mut a = target("a", "Standup", "https://meet.google.com/abc-defg-hij");
+        a.target_type = TargetType::Link;
+        let mut b = target("b", "Other", "https://meet.google.com/abcdefg-hij");
+        b.target_type = TargetType::Link;
+        let targets = vec![a.clone()];
+
+        assert!(find_conflict(&targets, &b).is_none());
+        a.id = "c".into();
+        assert_eq!(find_conflict(&targets, &a).unwrap().kind, ConflictKind::SameCode);
+    }
+
+    #[test]
+    fn test_conflict_serializes() {
+        let targets = vec![target("a", "Alice", "abcd")];
+        let conflict = find_conflict(&targets, &target("b", "Al", "ab-cd")).unwrap();
+        let json = serde_json::to_value(&conflict).unwrap();
+        assert_eq!(json["kind"], "same_room");
+        assert_eq!(json["existing_label"], "Alice");
+    }
+}
@@ -0,0 +1,203 @@
+// Private file and directory handling
+// What: Creates config dirs 0700 / files 0600 and audits existing ones
+// Why: settings.json, the keyfile and bundles hold pairing codes - other users must not read them
+// Used by:
+//   - SettingsStore::save() / load
+//   - storage::code_encryption keyfile creation
+//   - storage::bundle export
+// Change notes: Non-Unix platforms rely on per-user profile ACLs; checks are no-ops there
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Something about a settings file/directory that exposes it to other users
+/// What: One finding from check_private()
+/// Why: Callers decide whether to warn or refuse
+/// Used by: SettingsStore::security_warnings(), Tauri setup, CLI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityIssue {
+    /// Group or other permission bits are set
+    Accessible { path: PathBuf, mode: u32 },
+    /// Owned by a different user than the one running Blink
+    ForeignOwner { path: PathBuf, uid: u32 },
+}
+
+impl fmt::Display for SecurityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecurityIssue::Accessible { path, mode } => write!(
+                f,
+                "{:?} is accessible by other users (mode {:o}); run `chmod go-rwx` on it",
+                path, mode & 0o777
+            ),
+            SecurityIssue::ForeignOwner { path, uid } => write!(
+                f,
+                "{:?} is owned by another user (uid {})",
+                path, uid
+            ),
+        }
+    }
+}
+
+/// create_private_dir_all(path)
+/// What: Like fs::create_dir_all, but new directories are 0700 on Unix
+/// Why: ~/.config/blink must not be listable by other users
+/// Used by: SettingsStore::save(), keyfile creation, bundle export
+/// Change notes: Existing directories keep their mode - check_private() reports them
+pub fn create_private_dir_all(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        fs::DirBuilder::new().recursive(true).mode(0o700).create(path)
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::create_dir_all(path)
+    }
+}
+
+/// write_private_file(path, contents)
+/// What: Creates/truncates a file and writes it with mode 0600 on Unix
+/// Why: Files holding codes or keys must never be briefly world-readable
+/// Used by: SettingsStore::save() temp file, keyfile, bundle export
+pub fn write_private_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        // mode() only applies on creation; tighten leftovers from older versions too
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(contents)
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::write(path, contents)
+    }
+}
+
+/// check_private(path)
+/// What: Reports group/world access and foreign ownership for a file or directory
+/// Why: Loading settings someone else can read (or wrote) should not be silent
+/// Used by: SettingsStore load
+/// Contract:
+///   - Missing paths report nothing
+///   - Always empty on non-Unix platforms
+pub fn check_private(path: &Path) -> Vec<SecurityIssue> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let Ok(metadata) = fs::metadata(path) else {
+            return Vec::new();
+        };
+
+        let mut issues = Vec::new();
+        if metadata.mode() & 0o077 != 0 {
+            issues.push(SecurityIssue::Accessible { path: path.to_path_buf(), mode: metadata.mode() });
+        }
+
+        // SAFETY: geteuid has no preconditions and cannot fail
+        let current_uid = unsafe { libc::geteuid() };
+        if metadata.uid() != current_uid {
+            issues.push(SecurityIssue::ForeignOwner { path: path.to_path_buf(), uid: metadata.uid() });
+        }
+        issues
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Vec::new()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    fn mode_of(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn test_private_dir_created_0700() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+
+        create_private_dir_all(&nested).unwrap();
+        assert_eq!(mode_of(&nested), 0o700);
+        assert_eq!(mode_of(&temp_dir.path().join("a")), 0o700);
+    }
+
+    #[test]
+    fn test_private_file_0600() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("secret.json");
+
+        write_private_file(&path, b"{}").unwrap();
+        assert_eq!(mode_of(&path), 0o600);
+        assert_eq!(fs::read(&path).unwrap(), b"{}");
+    }
+
+    #[test]
+    fn test_private_file_tightens_existing() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("secret.json");
+        fs::write(&path, "old contents that are longer").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, b"new").unwrap();
+        assert_eq!(mode_of(&path), 0o600);
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+    }
+
+    #[test]
+    fn test_check_private() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("settings.json");
+
+        write_private_file(&path, b"{}").unwrap();
+        assert!(check_private(&path).is_empty());
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let issues = check_private(&path);
+        assert_eq!(issues, vec![SecurityIssue::Accessible { path: path.clone(), mode: fs::metadata(&path).unwrap().permissions().mode() }]);
+        assert!(issues[0].to_string().contains("644"));
+    }
+
+    // Edge case tests
+
+    #[test]
+    fn test_check_foreign_owner() {
+        // Only root can hand a file to another user
+        if unsafe { libc::geteuid() } != 0 {
+            return;
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("settings.json");
+        write_private_file(&path, b"{}").unwrap();
+        std::os::unix::fs::chown(&path, Some(65534), None).unwrap();
+
+        assert_eq!(check_private(&path), vec![SecurityIssue::ForeignOwner { path, uid: 65534 }]);
+    }
+
+    #[test]
+    fn test_check_missing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(check_private(&temp_dir.path().join("missing")).is_empty());
+    }
+}
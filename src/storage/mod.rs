@@ -5,6 +5,14 @@
 /// Change notes: If changing file format, implement migration
 
 pub mod settings_store;
+pub mod bundle;
+pub mod code_encryption;
+pub mod file_security;
+pub mod conflicts;
 
 // Re-export for convenience
 pub use settings_store::SettingsStore;
+pub use bundle::{Bundle, ImportOptions, ImportReport, MergeStrategy};
+pub use code_encryption::SettingsLocked;
+pub use file_security::SecurityIssue;
+pub use conflicts::{ConflictKind, TargetConflict};
@@ -7,11 +7,20 @@
 ///   - Target management to persist targets
 ///   - Tests for integration testing
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::models::{Settings, Target};
+use crate::core::{estimate_code_strength, generate_code_base32_100b, generate_id, normalize_channel_name, parse_meeting_link, Timestamp};
+use crate::models::settings::{AppSettings, Keybinds};
+use crate::models::{Channel, KeySource, NewTarget, RecentCall, Settings, Target, TargetType, WindowGeometry};
+use super::bundle::{self, Bundle, ImportOptions, ImportReport};
+use super::code_encryption::{self, CodeKey};
+use super::conflicts::{self, TargetConflict};
+use super::file_security::{self, SecurityIssue};
+
+/// Length of the Recent list (tray menu)
+pub const MAX_RECENT_CALLS: usize = 5;
 
 /// Settings store that manages persistence
 /// What: Handles all settings I/O operations
@@ -22,6 +31,14 @@ pub struct SettingsStore {
     settings: Settings,
     /// Path to settings file
     file_path: PathBuf,
+    /// Key for encrypted pairing codes (when settings.code_encryption is set)
+    code_key: Option<CodeKey>,
+    /// Passphrase-protected file not yet unlocked; saving is refused
+    locked: bool,
+    /// Permission/ownership problems found when loading
+    security_warnings: Vec<SecurityIssue>,
+    /// Values that loaded but aren't valid (e.g. unparseable created_at)
+    data_warnings: Vec<String>,
 }
 
 impl SettingsStore {
@@ -32,13 +49,19 @@ impl SettingsStore {
     /// Calls: dirs::config_dir(), Self::load_from_path()
     /// Change notes: If dirs crate changes API, update path resolution
     pub fn load() -> Result<Self> {
+        Self::load_from_path(Self::default_path()?)
+    }
+    
+    /// Default settings file location
+    /// What: Resolves ~/.config/blink/settings.json (or the OS equivalent)
+    /// Why: The app and the CLI must agree on where settings live
+    /// Used by: load(), CLI `--config` fallback
+    /// Calls: dirs::config_dir()
+    pub fn default_path() -> Result<PathBuf> {
         let config_dir = dirs::config_dir()
             .context("Failed to determine config directory")?;
         
-        let app_dir = config_dir.join("blink");
-        let file_path = app_dir.join("settings.json");
-        
-        Self::load_from_path(file_path)
+        Ok(config_dir.join("blink").join("settings.json"))
     }
     
     /// Load settings from specific path
@@ -46,24 +69,136 @@ impl SettingsStore {
     /// Why: Allows testing with temp directories
     /// Used by: load(), tests
     /// Calls: fs::read_to_string, serde_json::from_str
+    /// Change notes: Passphrase-protected files fail with SettingsLocked
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let file_path = path.as_ref().to_path_buf();
+        Self::open(path.as_ref(), None)
+    }
+    
+    /// Load passphrase-protected settings
+    /// What: Same as load_from_path() but can unlock passphrase-encrypted codes
+    /// Why: Passphrase-derived keys can't be found on disk
+    /// Used by: Tauri unlock command, CLI (BLINK_PASSPHRASE)
+    pub fn load_from_path_with_passphrase<P: AsRef<Path>>(path: P, passphrase: &str) -> Result<Self> {
+        Self::open(path.as_ref(), Some(passphrase))
+    }
+    
+    fn open(file_path: &Path, passphrase: Option<&str>) -> Result<Self> {
+        let file_path = file_path.to_path_buf();
         
-        let settings = if file_path.exists() {
+        let mut security_warnings = Vec::new();
+        let mut settings: Settings = if file_path.exists() {
             let contents = fs::read_to_string(&file_path)
                 .with_context(|| format!("Failed to read settings from {:?}", file_path))?;
             
-            serde_json::from_str(&contents)
-                .with_context(|| format!("Failed to parse settings from {:?}", file_path))?
+            let settings: Settings = serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse settings from {:?}", file_path))?;
+            
+            security_warnings = Self::audit_permissions(&file_path);
+            if settings.app_settings.strict_permissions && !security_warnings.is_empty() {
+                let details: Vec<String> = security_warnings.iter().map(|i| i.to_string()).collect();
+                bail!("Refusing to load insecure settings: {}", details.join("; "));
+            }
+            settings
         } else {
             // File doesn't exist, use defaults
             Settings::default()
         };
         
-        Ok(Self {
+        let mut code_key = None;
+        let mut needs_migration = false;
+        if let Some(info) = &settings.code_encryption {
+            let key = code_encryption::unlock(info, &file_path, passphrase)?;
+            // Plaintext codes in an encrypted file (hand edits, older saves) get encrypted below
+            needs_migration = code_encryption::decrypt_codes(&mut settings, &key)? > 0;
+            code_key = Some(key);
+        }
+        
+        let data_warnings = Self::audit_data(&settings);
+        let store = Self {
             settings,
             file_path,
-        })
+            code_key,
+            locked: false,
+            security_warnings,
+            data_warnings,
+        };
+        
+        if needs_migration {
+            store.save()?;
+        }
+        
+        Ok(store)
+    }
+    
+    /// Create a locked placeholder store
+    /// What: Default settings that refuse every change (see ensure_unlocked())
+    /// Why: App can start and ask for the passphrase without clobbering the real file
+    /// Used by: Tauri setup when load fails with SettingsLocked
+    pub fn new_locked<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            locked: true,
+            ..Self::new_with_path(path)
+        }
+    }
+    
+    /// Check if the store is a locked placeholder
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+    
+    /// Fails with SettingsLocked on a locked placeholder
+    /// Why: Mutators call it before touching anything - a change that can't be
+    ///      saved must not show up in memory (tray menu, hotkeys, joins)
+    fn ensure_unlocked(&self) -> Result<()> {
+        if self.locked {
+            return Err(code_encryption::SettingsLocked.into());
+        }
+        Ok(())
+    }
+    
+    /// Permission problems found at load
+    /// What: Group/world access or foreign ownership of settings.json or its directory
+    /// Why: Callers surface these as warnings (app log, CLI stderr)
+    /// Used by: Tauri setup, CLI
+    pub fn security_warnings(&self) -> &[SecurityIssue] {
+        &self.security_warnings
+    }
+    
+    /// Invalid values found at load
+    /// What: Human-readable descriptions, one per problem
+    /// Why: Bad legacy values load anyway (one field shouldn't lose the whole file),
+    ///      but the user should hear about them
+    /// Used by: Tauri setup, CLI
+    pub fn data_warnings(&self) -> &[String] {
+        &self.data_warnings
+    }
+    
+    /// Targets that share a pairing code or derived room
+    /// What: One conflict per later target colliding with an earlier one
+    /// Why: Hand edits and older versions could store duplicates; the UI offers merge_targets()
+    /// Used by: Tauri get_target_conflicts command, audit_data()
+    pub fn conflicts(&self) -> Vec<TargetConflict> {
+        conflicts::find_all_conflicts(&self.settings.targets)
+    }
+    
+    /// Validate loaded values that deserialization accepted leniently
+    fn audit_data(settings: &Settings) -> Vec<String> {
+        let timestamps = settings.targets.iter()
+            .filter(|t| !t.created_at.is_valid())
+            .map(|t| format!("Target '{}' ({}) has an invalid created_at '{}'", t.label, t.id, t.created_at));
+        let rooms = conflicts::find_all_conflicts(&settings.targets).into_iter()
+            .map(|c| format!("Target '{}' ({}): {}", c.target_label, c.target_id, c));
+        timestamps.chain(rooms).collect()
+    }
+    
+    /// Check the settings file, its keyfile and its directory
+    fn audit_permissions(file_path: &Path) -> Vec<SecurityIssue> {
+        let mut issues = file_security::check_private(file_path);
+        issues.extend(file_security::check_private(&code_encryption::keyfile_path(file_path)));
+        if let Some(parent) = file_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            issues.extend(file_security::check_private(parent));
+        }
+        issues
     }
     
     /// Create new store with specific path
@@ -74,6 +209,10 @@ impl SettingsStore {
         Self {
             settings: Settings::default(),
             file_path: path.as_ref().to_path_buf(),
+            code_key: None,
+            locked: false,
+            security_warnings: Vec::new(),
+            data_warnings: Vec::new(),
         }
     }
     
@@ -81,22 +220,33 @@ impl SettingsStore {
     /// What: Persists settings to JSON file
     /// Why: User changes need to be saved
     /// Used by: Settings UI save button, add/remove target
-    /// Calls: fs::create_dir_all, serde_json::to_string_pretty, fs::write
+    /// Calls: file_security::{create_private_dir_all, write_private_file}, serde_json::to_string_pretty
     /// Change notes: If changing format, ensure backwards compatibility
     pub fn save(&self) -> Result<()> {
-        // Ensure directory exists
+        self.ensure_unlocked()?;
+        
+        // Ensure directory exists (0700 on Unix when we create it)
         if let Some(parent) = self.file_path.parent() {
-            fs::create_dir_all(parent)
+            file_security::create_private_dir_all(parent)
                 .with_context(|| format!("Failed to create directory {:?}", parent))?;
         }
         
+        // Encrypt codes on a copy - memory always holds plaintext
+        let mut on_disk = self.settings.clone();
+        if on_disk.code_encryption.is_some() {
+            let key = self.code_key.as_ref()
+                .context("Code encryption is enabled but no key is loaded")?;
+            code_encryption::encrypt_codes(&mut on_disk, key)?;
+        }
+        
         // Serialize to pretty JSON
-        let json = serde_json::to_string_pretty(&self.settings)
+        let json = serde_json::to_string_pretty(&on_disk)
             .context("Failed to serialize settings")?;
         
         // Write atomically (write to temp, then rename)
         let temp_path = self.file_path.with_extension("json.tmp");
-        fs::write(&temp_path, json)
+        // Temp file is 0600 from the start; rename keeps the mode
+        file_security::write_private_file(&temp_path, json.as_bytes())
             .with_context(|| format!("Failed to write settings to {:?}", temp_path))?;
         
         fs::rename(&temp_path, &self.file_path)
@@ -121,29 +271,111 @@ impl SettingsStore {
         self.settings.targets.iter().find(|t| t.is_primary)
     }
     
+    /// Find target by id or label
+    /// What: Resolves a user-typed reference to a target
+    /// Why: People remember labels ("Alice"), scripts use ids
+    /// Used by: CLI `blink join`
+    /// Change notes: Exact id wins over label; labels match case-insensitively
+    pub fn find_target(&self, query: &str) -> Option<&Target> {
+        self.get_target(query).or_else(|| {
+            self.settings.targets.iter()
+                .find(|t| t.label.eq_ignore_ascii_case(query))
+        })
+    }
+    
+    /// Find a channel by id
+    /// What: Returns the channel and the target whose code it derives from
+    /// Why: Channel hotkeys and tray items carry only the channel id
+    /// Used by: Tauri JoinChannel hotkey and tray menu
+    pub fn find_channel(&self, channel_id: &str) -> Option<(&Target, &Channel)> {
+        self.settings.targets.iter().find_map(|target| {
+            target.channels.iter()
+                .find(|c| c.id == channel_id)
+                .map(|channel| (target, channel))
+        })
+    }
+    
+    /// Create a target from user-supplied fields
+    /// What: Generates id, created_at (and code when missing), then add_target()
+    /// Why: Callers shouldn't invent ids - the store guarantees they're unique
+    /// Used by: Tauri add_target command, CLI `target add`
+    /// Calls: add_target()
+    /// Contract: Link targets must bring their URL; no code is generated for them
+    pub fn create_target(&mut self, new_target: NewTarget) -> Result<Target> {
+        self.ensure_unlocked()?;
+        if new_target.target_type == TargetType::Link && new_target.code.is_none() {
+            bail!("Link targets need a meeting URL");
+        }
+        let mut id = generate_id("tg");
+        while self.get_target(&id).is_some() {
+            id = generate_id("tg");
+        }
+        
+        let target = Target {
+            id,
+            label: new_target.label,
+            code: new_target.code.unwrap_or_else(|| generate_code_base32_100b().into()),
+            target_type: new_target.target_type,
+            is_primary: new_target.is_primary,
+            call_defaults: new_target.call_defaults,
+            created_at: Timestamp::now(),
+            notes: new_target.notes,
+            channels: new_target.channels,
+        };
+        
+        let id = target.id.clone();
+        self.add_target(target)?;
+        self.get_target(&id).cloned().context("Target missing after add")
+    }
+    
     /// Add a new target
-    /// What: Adds target and saves to disk
+    /// What: Validates and adds target, then saves to disk
     /// Why: User adds new call partners
-    /// Used by: Settings UI, invite acceptance
+    /// Used by: create_target(), invite acceptance
     /// Calls: save()
+    /// Contract:
+    ///   - Rejects empty labels/codes, duplicate ids, and codes that share a
+    ///     room with another target (error downcasts to TargetConflict)
+    ///   - Rejects codes weaker than app_settings.min_code_strength
+    ///   - Link targets: code must be a valid meeting URL (stored normalized)
+    ///     and they can't have channels
+    ///   - First target, or one marked is_primary, becomes the only primary
     pub fn add_target(&mut self, target: Target) -> Result<()> {
-        // If this is the first target, make it primary
+        self.ensure_unlocked()?;
         let mut target = target;
+        Self::assign_channel_ids(&mut target);
+        Self::normalize_link(&mut target)?;
+        Self::validate_target(&self.settings, &target)?;
+        Self::check_code_strength(&self.settings, &target)?;
+        if self.settings.targets.iter().any(|t| t.id == target.id) {
+            bail!("A target with id '{}' already exists", target.id);
+        }
+        
+        // If this is the first target, make it primary
         if self.settings.targets.is_empty() {
             target.is_primary = true;
         }
+        if target.is_primary {
+            for existing in &mut self.settings.targets {
+                existing.is_primary = false;
+            }
+        }
         
         self.settings.targets.push(target);
         self.save()
     }
     
     /// Remove target by ID
-    /// What: Removes target and saves
+    /// What: Removes target and its target/channel hotkeys, then saves
     /// Why: User removes partners
     /// Used by: Settings UI remove button
     /// Calls: save()
     pub fn remove_target(&mut self, id: &str) -> Result<bool> {
+        self.ensure_unlocked()?;
         let initial_len = self.settings.targets.len();
+        let channel_ids: Vec<String> = self.get_target(id)
+            .map(|t| t.channels.iter().map(|c| c.id.clone()).collect())
+            .unwrap_or_default();
         self.settings.targets.retain(|t| t.id != id);
         
         if self.settings.targets.len() < initial_len {
@@ -153,6 +385,12 @@ impl SettingsStore {
                 self.settings.targets[0].is_primary = true;
             }
             
+            // A hotkey for a target that no longer exists would join nothing
+            self.settings.keybinds.target_hotkeys.remove(id);
+            for channel_id in &channel_ids {
+                self.settings.keybinds.channel_hotkeys.remove(channel_id);
+            }
+            
             self.save()?;
             Ok(true)
         } else {
@@ -163,15 +401,401 @@ impl SettingsStore {
     /// Update existing target
     /// What: Updates target properties
     /// Why: User edits target settings
-    /// Used by: Settings UI edit
+    /// Used by: Settings UI edit, Tauri update_target command
+    /// Contract:
+    ///   - Returns Ok(false) if no target has this id
+    ///   - created_at is kept from the stored target
+    ///   - A changed code must meet app_settings.min_code_strength (an unchanged
+    ///     weak code from an older version is left alone)
+    ///   - is_primary = true makes it the only primary; un-setting the only
+    ///     primary is ignored (use set_primary_target on another target)
+    ///   - New channels get ids; hotkeys of dropped channels are removed
     pub fn update_target(&mut self, target: Target) -> Result<bool> {
-        if let Some(existing) = self.settings.targets.iter_mut().find(|t| t.id == target.id) {
-            *existing = target;
-            self.save()?;
-            Ok(true)
-        } else {
-            Ok(false)
+        self.ensure_unlocked()?;
+        let Some(index) = self.settings.targets.iter().position(|t| t.id == target.id) else {
+            return Ok(false);
+        };
+        let mut target = target;
+        Self::assign_channel_ids(&mut target);
+        Self::normalize_link(&mut target)?;
+        Self::validate_target(&self.settings, &target)?;
+        if target.code != self.settings.targets[index].code {
+            Self::check_code_strength(&self.settings, &target)?;
+        }
+        
+        let existing = &self.settings.targets[index];
+        target.created_at = existing.created_at.clone();
+        target.call_defaults.window = existing.call_defaults.window.clone();
+        target.is_primary = target.is_primary || existing.is_primary;
+        for dropped in existing.channels.iter().filter(|c| !target.channels.iter().any(|n| n.id == c.id)) {
+            self.settings.keybinds.channel_hotkeys.remove(&dropped.id);
+        }
+        
+        if target.is_primary {
+            for other in &mut self.settings.targets {
+                other.is_primary = false;
+            }
+        }
+        self.settings.targets[index] = target;
+        self.save()?;
+        Ok(true)
+    }
+    
+    /// Record a join for the Recent list
+    /// What: Moves (target, channel) to the front of recent_calls, keeping at
+    ///       most MAX_RECENT_CALLS entries, and saves
+    /// Used by: Tauri join handler (hotkeys, tray)
+    /// Contract: Unknown target/channel ids are ignored (Ok(false))
+    pub fn record_recent_call(&mut self, target_id: &str, channel_id: Option<&str>) -> Result<bool> {
+        self.ensure_unlocked()?;
+        let known = match channel_id {
+            Some(channel_id) => self.find_channel(channel_id).is_some_and(|(t, _)| t.id == target_id),
+            None => self.get_target(target_id).is_some(),
+        };
+        if !known {
+            return Ok(false);
+        }
+        
+        let recent = &mut self.settings.recent_calls;
+        recent.retain(|r| !(r.target_id == target_id && r.channel_id.as_deref() == channel_id));
+        recent.insert(0, RecentCall {
+            target_id: target_id.to_string(),
+            channel_id: channel_id.map(str::to_string),
+            joined_at: Timestamp::now(),
+        });
+        recent.truncate(MAX_RECENT_CALLS);
+        self.save()?;
+        Ok(true)
+    }
+    
+    /// Recent joins that still exist, newest first
+    /// What: recent_calls resolved to their target and channel; entries for
+    ///       removed targets/channels are skipped
+    /// Used by: tray Recent submenu
+    pub fn recent_calls(&self) -> Vec<(&Target, Option<&Channel>)> {
+        self.settings.recent_calls.iter()
+            .filter_map(|recent| {
+                let target = self.get_target(&recent.target_id)?;
+                match &recent.channel_id {
+                    Some(channel_id) => {
+                        let channel = target.channels.iter().find(|c| c.id == *channel_id)?;
+                        Some((target, Some(channel)))
+                    }
+                    None => Some((target, None)),
+                }
+            })
+            .collect()
+    }
+    
+    /// Remember a target's conference window geometry
+    /// What: Sets call_defaults.window and saves
+    /// Why: The settings UI edits targets with whatever it loaded; the window
+    ///      geometry is only ever written here (update_target keeps it)
+    /// Used by: Tauri ConferenceWindow when the window closes
+    pub fn set_window_geometry(&mut self, target_id: &str, geometry: WindowGeometry) -> Result<bool> {
+        self.ensure_unlocked()?;
+        let Some(target) = self.settings.targets.iter_mut().find(|t| t.id == target_id) else {
+            return Ok(false);
+        };
+        if target.call_defaults.window.as_ref() == Some(&geometry) {
+            return Ok(true);
+        }
+        target.call_defaults.window = Some(geometry);
+        self.save()?;
+        Ok(true)
+    }
+    
+    /// Replace the app settings and keybinds
+    /// What: Sets app_settings and keybinds and saves; targets, code
+    ///       encryption and the Recent list are untouched
+    /// Why: Preference editors (settings page, CLI `settings set`) work on a
+    ///      copy; writing the whole copy back would undo target changes made
+    ///      meanwhile and bypass target validation
+    /// Used by: Tauri save_settings command, CLI `settings set`
+    /// Contract: A locked store fails with SettingsLocked and is left unchanged
+    pub fn update_preferences(&mut self, app_settings: AppSettings, keybinds: Keybinds) -> Result<()> {
+        self.ensure_unlocked()?;
+        self.settings.app_settings = app_settings;
+        self.settings.keybinds = keybinds;
+        self.save()
+    }
+    
+    /// Make a target the primary one
+    /// What: Sets is_primary on the given target and clears it on all others
+    /// Why: Exactly one target should answer the join-primary hotkey
+    /// Used by: CLI `target set-primary`, Tauri set_primary_target command
+    /// Calls: save()
+    pub fn set_primary_target(&mut self, id: &str) -> Result<bool> {
+        self.ensure_unlocked()?;
+        if self.get_target(id).is_none() {
+            return Ok(false);
+        }
+        
+        for target in &mut self.settings.targets {
+            target.is_primary = target.id == id;
+        }
+        
+        self.save()?;
+        Ok(true)
+    }
+    
+    /// Reorder targets
+    /// What: Puts targets in the order of the given ids
+    /// Why: Display order in the settings list and tray menu
+    /// Used by: Tauri reorder_targets command
+    /// Contract:
+    ///   - ids must contain every target id exactly once
+    pub fn reorder_targets(&mut self, ids: &[String]) -> Result<()> {
+        self.ensure_unlocked()?;
+        let mut sorted_ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let mut current_ids: Vec<&str> = self.settings.targets.iter().map(|t| t.id.as_str()).collect();
+        sorted_ids.sort_unstable();
+        current_ids.sort_unstable();
+        if sorted_ids != current_ids {
+            bail!("Reorder must list every target id exactly once");
         }
+        
+        self.settings.targets.sort_by_key(|t| ids.iter().position(|id| *id == t.id));
+        self.save()
+    }
+    
+    /// Add a named channel to a target
+    /// What: Appends a channel (sub-room) and saves
+    /// Why: Separate calls with the same partner without a new code
+    /// Used by: Tauri add_channel command
+    /// Contract:
+    ///   - Name must be non-empty and unique (after normalization) within the target
+    pub fn add_channel(&mut self, target_id: &str, name: &str) -> Result<Channel> {
+        self.ensure_unlocked()?;
+        let mut target = self.get_target(target_id)
+            .with_context(|| format!("No target with id '{}'", target_id))?
+            .clone();
+        target.channels.push(Channel { id: String::new(), name: name.trim().to_string() });
+        
+        self.update_target(target)?;
+        self.get_target(target_id)
+            .and_then(|t| t.channels.last().cloned())
+            .context("Channel missing after add")
+    }
+    
+    /// Remove a channel and its hotkey
+    /// Used by: Tauri remove_channel command
+    pub fn remove_channel(&mut self, target_id: &str, channel_id: &str) -> Result<bool> {
+        self.ensure_unlocked()?;
+        let Some(target) = self.settings.targets.iter_mut().find(|t| t.id == target_id) else {
+            return Ok(false);
+        };
+        let initial_len = target.channels.len();
+        target.channels.retain(|c| c.id != channel_id);
+        if target.channels.len() == initial_len {
+            return Ok(false);
+        }
+        
+        self.settings.keybinds.channel_hotkeys.remove(channel_id);
+        self.save()?;
+        Ok(true)
+    }
+    
+    /// Give channels added without an id (UI, NewTarget) a fresh one
+    pub(super) fn assign_channel_ids(target: &mut Target) {
+        for channel in target.channels.iter_mut().filter(|c| c.id.trim().is_empty()) {
+            channel.id = generate_id("ch");
+        }
+    }
+    
+    /// Validate a Link target's URL and store it normalized (lowercase host, https:// added)
+    pub(super) fn normalize_link(target: &mut Target) -> Result<()> {
+        if target.target_type == TargetType::Link {
+            let link = parse_meeting_link(target.code.expose())?;
+            target.code = link.url.into();
+        }
+        Ok(())
+    }
+    
+    /// Checks shared by add_target(), update_target() and bundle imports
+    /// (against `settings`, which may be a planned copy)
+    pub(super) fn validate_target(settings: &Settings, target: &Target) -> Result<()> {
+        if target.id.trim().is_empty() {
+            bail!("Target id must not be empty");
+        }
+        // Ids end up in the settings page's markup and in hotkey maps
+        if !is_valid_id(&target.id) {
+            bail!("Target id '{}' may only contain letters, digits, '-' and '_'", target.id);
+        }
+        if target.label.trim().is_empty() {
+            bail!("Target label must not be empty");
+        }
+        if target.code.expose().trim().is_empty() {
+            bail!("Pairing code must not be empty");
+        }
+        if let Some(conflict) = conflicts::find_conflict(&settings.targets, target) {
+            return Err(conflict.into());
+        }
+        if target.target_type == TargetType::Link && !target.channels.is_empty() {
+            bail!("Link targets open a fixed URL and can't have channels");
+        }
+        
+        let mut names: Vec<String> = Vec::new();
+        for (i, channel) in target.channels.iter().enumerate() {
+            if !is_valid_id(&channel.id) {
+                bail!("Channel id '{}' may only contain letters, digits, '-' and '_'", channel.id);
+            }
+            if target.channels[..i].iter().any(|c| c.id == channel.id) {
+                bail!("Target '{}' has two channels with id '{}'", target.label, channel.id);
+            }
+            let name = normalize_channel_name(&channel.name);
+            if name.is_empty() {
+                bail!("Channel name must not be empty");
+            }
+            if names.contains(&name) {
+                bail!("Target '{}' already has a channel named '{}'", target.label, channel.name.trim());
+            }
+            names.push(name);
+        }
+        let other_channels = settings.targets.iter()
+            .filter(|t| t.id != target.id)
+            .flat_map(|t| &t.channels);
+        for channel in other_channels {
+            if target.channels.iter().any(|c| c.id == channel.id) {
+                bail!("Channel id '{}' is already used by another target", channel.id);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Reject custom codes an attacker could guess (Link URLs are chosen by their provider)
+    pub(super) fn check_code_strength(settings: &Settings, target: &Target) -> Result<()> {
+        if target.target_type == TargetType::Link {
+            return Ok(());
+        }
+        let required = settings.app_settings.min_code_strength;
+        let estimate = estimate_code_strength(target.code.expose());
+        if estimate.strength < required {
+            bail!(
+                "Pairing code is too weak ({}, ~{:.0} bits; at least {} needs {:.0}). \
+                 Use more uncommon words or generate a code",
+                estimate.strength, estimate.bits, required, required.min_bits()
+            );
+        }
+        Ok(())
+    }
+    
+    /// Merge two targets that join the same room
+    /// What: Folds remove_id into keep_id (labels, notes, hotkey, primary) and deletes it
+    /// Why: Resolves a TargetConflict without losing what the user typed
+    /// Used by: Tauri merge_targets command, CLI `target merge`
+    /// Calls: conflicts::merge_into(), save()
+    /// Contract:
+    ///   - Both targets must exist and share a derived room
+    ///   - keep_id's code, type and call defaults win
+    ///   - keep_id's hotkey wins; if it has none it inherits remove_id's
+    ///   - Channels are combined; a same-named channel keeps keep_id's id
+    pub fn merge_targets(&mut self, keep_id: &str, remove_id: &str) -> Result<Target> {
+        self.ensure_unlocked()?;
+        if keep_id == remove_id {
+            bail!("Cannot merge a target with itself");
+        }
+        let other = self.get_target(remove_id).cloned()
+            .with_context(|| format!("No target with id '{}'", remove_id))?;
+        let keep = self.settings.targets.iter().find(|t| t.id == keep_id)
+            .with_context(|| format!("No target with id '{}'", keep_id))?;
+        if conflicts::find_conflict(std::slice::from_ref(keep), &other).is_none() {
+            bail!("Targets '{}' and '{}' don't share a room", keep.label, other.label);
+        }
+        
+        let hotkeys = &mut self.settings.keybinds.target_hotkeys;
+        if let Some(hotkey) = hotkeys.remove(remove_id) {
+            hotkeys.entry(keep_id.to_string()).or_insert(hotkey);
+        }
+        
+        // Channels keep their ids, so only same-named duplicates need their hotkey moved
+        let channel_hotkeys = &mut self.settings.keybinds.channel_hotkeys;
+        for (dropped, kept) in conflicts::duplicate_channels(keep, &other) {
+            if let Some(hotkey) = channel_hotkeys.remove(&dropped.id) {
+                channel_hotkeys.entry(kept.id.clone()).or_insert(hotkey);
+            }
+        }
+        
+        self.settings.targets.retain(|t| t.id != remove_id);
+        let mut merged = None;
+        if let Some(keep) = self.settings.targets.iter_mut().find(|t| t.id == keep_id) {
+            conflicts::merge_into(keep, &other);
+            merged = Some(keep.clone());
+        }
+        
+        self.save()?;
+        merged.context("Merged target disappeared")
+    }
+    
+    /// Export a bundle
+    /// What: Snapshot of targets, plus settings/keybinds when asked
+    /// Why: Moving to a new machine or sharing targets
+    /// Used by: export_to_path(), Tauri export command
+    pub fn export_bundle(&self, include_settings: bool) -> Bundle {
+        bundle::build_bundle(&self.settings, include_settings)
+    }
+    
+    /// Export a bundle to a file
+    /// What: Writes export_bundle() as JSON, encrypted if a passphrase is given
+    /// Why: Bundles contain pairing codes; encryption protects them in transit
+    /// Used by: Tauri export command
+    /// Calls: bundle::write_bundle()
+    pub fn export_to_path<P: AsRef<Path>>(&self, path: P, include_settings: bool, passphrase: Option<&str>) -> Result<()> {
+        bundle::write_bundle(path.as_ref(), &self.export_bundle(include_settings), passphrase)
+    }
+    
+    /// Preview an import
+    /// What: Dry-run of import_bundle() - nothing is changed or saved
+    /// Why: Settings UI shows the diff before the user confirms
+    /// Used by: Tauri preview command
+    pub fn preview_import(&self, bundle: &Bundle, options: &ImportOptions) -> ImportReport {
+        bundle::plan_import(&self.settings, bundle, options).1
+    }
+    
+    /// Import a bundle
+    /// What: Merges bundle targets (and optionally settings) and saves
+    /// Why: Restores targets on a new machine
+    /// Used by: Tauri import command
+    /// Calls: bundle::plan_import(), save()
+    pub fn import_bundle(&mut self, bundle: &Bundle, options: &ImportOptions) -> Result<ImportReport> {
+        self.ensure_unlocked()?;
+        let (next, report) = bundle::plan_import(&self.settings, bundle, options);
+        self.settings = next;
+        self.save()?;
+        Ok(report)
+    }
+    
+    /// Enable pairing-code encryption
+    /// What: Creates a key (keyfile or passphrase) and re-saves with encrypted codes
+    /// Why: This is also the migration for existing plaintext settings files
+    /// Used by: Settings UI, CLI `encryption enable`
+    /// Calls: code_encryption::setup(), save()
+    /// Change notes: Switching method re-encrypts everything under the new key
+    pub fn enable_code_encryption(&mut self, method: KeySource, passphrase: Option<&str>) -> Result<()> {
+        self.ensure_unlocked()?;
+        let (info, key) = code_encryption::setup(method, passphrase, &self.file_path)?;
+        self.settings.code_encryption = Some(info);
+        self.code_key = Some(key);
+        self.save()
+    }
+    
+    /// Disable pairing-code encryption
+    /// What: Re-saves with plaintext codes
+    /// Used by: Settings UI, CLI `encryption disable`
+    /// Calls: save()
+    pub fn disable_code_encryption(&mut self) -> Result<()> {
+        self.ensure_unlocked()?;
+        self.settings.code_encryption = None;
+        self.code_key = None;
+        self.save()
+    }
+    
+    /// Get file path
+    /// What: Returns where this store reads and writes
+    /// Why: CLI and error messages show the active config file
+    /// Used by: CLI output
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
     }
     
     /// Get all targets
@@ -199,22 +823,30 @@ impl SettingsStore {
     }
 }
 
+/// Target/channel ids: what generate_id() makes, plus older hand-written ones
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
     use crate::models::{TargetType, CallDefaults};
+    use super::super::conflicts::ConflictKind;
+    use crate::core::CodeStrength;
     
     fn create_test_target(id: &str) -> Target {
         Target {
             id: id.to_string(),
             label: format!("Test {}", id),
-            code: format!("test-code-{}", id),
+            code: format!("test-code-{}-qzvx", id).into(),
             target_type: TargetType::Person,
             is_primary: false,
             call_defaults: CallDefaults::default(),
-            created_at: "2024-01-01T00:00:00Z".to_string(),
+            created_at: "2024-01-01T00:00:00Z".parse().unwrap(),
             notes: None,
+            channels: Vec::new(),
         }
     }
     
@@ -315,8 +947,352 @@ mod tests {
         assert!(!store.update_target(create_test_target("999")).unwrap());
     }
     
+    #[test]
+    fn test_find_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        store.add_target(create_test_target("2")).unwrap();
+        
+        assert_eq!(store.find_target("2").unwrap().id, "2");
+        assert_eq!(store.find_target("test 1").unwrap().id, "1");
+        assert!(store.find_target("nobody").is_none());
+    }
+    
+    #[test]
+    fn test_set_primary_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.add_target(create_test_target("1")).unwrap(); // Will be primary
+        store.add_target(create_test_target("2")).unwrap();
+        
+        assert!(store.set_primary_target("2").unwrap());
+        assert!(!store.get_target("1").unwrap().is_primary);
+        assert_eq!(store.get_primary_target().unwrap().id, "2");
+        
+        // Persisted to disk
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(loaded.get_primary_target().unwrap().id, "2");
+        
+        // Unknown id leaves primary untouched
+        assert!(!store.set_primary_target("999").unwrap());
+        assert_eq!(store.get_primary_target().unwrap().id, "2");
+    }
+    
+    #[test]
+    fn test_create_target_generates_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        
+        let alice = store.create_target(NewTarget { label: "Alice".into(), ..NewTarget::default() }).unwrap();
+        assert!(alice.id.starts_with("tg_"));
+        assert!(alice.created_at.is_valid());
+        assert_eq!(alice.code.expose().len(), 24);
+        assert!(alice.is_primary);
+        
+        // is_primary on a later target moves the primary
+        let bob = store.create_target(NewTarget {
+            label: "Bob".into(),
+            code: Some("bob-code-qzvx".into()),
+            is_primary: true,
+            ..NewTarget::default()
+        }).unwrap();
+        assert_ne!(bob.id, alice.id);
+        assert_eq!(store.get_primary_target().unwrap().id, bob.id);
+        assert_eq!(store.get_targets().iter().filter(|t| t.is_primary).count(), 1);
+    }
+    
+    #[test]
+    fn test_update_target_keeps_single_primary() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        store.add_target(create_test_target("2")).unwrap();
+        
+        let mut second = store.get_target("2").unwrap().clone();
+        second.is_primary = true;
+        second.created_at = "1999-01-01T00:00:00Z".parse().unwrap();
+        assert!(store.update_target(second).unwrap());
+        assert_eq!(store.get_primary_target().unwrap().id, "2");
+        assert!(!store.get_target("1").unwrap().is_primary);
+        assert_eq!(store.get_target("2").unwrap().created_at.to_string(), "2024-01-01T00:00:00Z");
+        
+        // Un-setting the only primary is ignored
+        let mut second = store.get_target("2").unwrap().clone();
+        second.is_primary = false;
+        store.update_target(second).unwrap();
+        assert_eq!(store.get_primary_target().unwrap().id, "2");
+    }
+    
+    #[test]
+    fn test_reorder_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        let mut store = SettingsStore::new_with_path(&file_path);
+        for id in ["1", "2", "3"] {
+            store.add_target(create_test_target(id)).unwrap();
+        }
+        
+        store.reorder_targets(&["3".into(), "1".into(), "2".into()]).unwrap();
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        let ids: Vec<&str> = loaded.get_targets().iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["3", "1", "2"]);
+        
+        assert!(store.reorder_targets(&["3".into(), "1".into()]).is_err());
+        assert!(store.reorder_targets(&["3".into(), "1".into(), "1".into()]).is_err());
+        assert!(store.reorder_targets(&["3".into(), "1".into(), "9".into()]).is_err());
+    }
+    
+    #[test]
+    fn test_remove_target_cleans_hotkey() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        store.add_target(create_test_target("2")).unwrap();
+        let hotkeys = &mut store.settings_mut().keybinds.target_hotkeys;
+        hotkeys.insert("1".into(), "Ctrl+Alt+1".into());
+        hotkeys.insert("2".into(), "Ctrl+Alt+2".into());
+        
+        store.remove_target("1").unwrap();
+        let hotkeys = &store.settings().keybinds.target_hotkeys;
+        assert!(!hotkeys.contains_key("1"));
+        assert!(hotkeys.contains_key("2"));
+    }
+    
+    #[test]
+    fn test_export_import_between_stores() {
+        let temp_dir = TempDir::new().unwrap();
+        let bundle_path = temp_dir.path().join("bundle.json");
+        
+        let mut old_machine = SettingsStore::new_with_path(temp_dir.path().join("old.json"));
+        old_machine.add_target(create_test_target("1")).unwrap();
+        old_machine.add_target(create_test_target("2")).unwrap();
+        old_machine.export_to_path(&bundle_path, true, Some("passphrase")).unwrap();
+        
+        let mut new_machine = SettingsStore::new_with_path(temp_dir.path().join("new.json"));
+        let bundle = bundle::read_bundle(&bundle_path, Some("passphrase")).unwrap();
+        let options = ImportOptions::default();
+        
+        // Preview changes nothing
+        let preview = new_machine.preview_import(&bundle, &options);
+        assert_eq!(preview.applied_count(), 2);
+        assert!(new_machine.get_targets().is_empty());
+        
+        let report = new_machine.import_bundle(&bundle, &options).unwrap();
+        assert_eq!(report, preview);
+        
+        let loaded = SettingsStore::load_from_path(temp_dir.path().join("new.json")).unwrap();
+        assert_eq!(loaded.get_targets(), old_machine.get_targets());
+    }
+    
+    #[test]
+    fn test_encrypted_codes_with_keyfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        // Existing plaintext file...
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.add_target(create_test_target("1")).unwrap();
+        assert!(fs::read_to_string(&file_path).unwrap().contains("test-code-1-qzvx"));
+        
+        // ...migrated by enabling encryption
+        store.enable_code_encryption(KeySource::Keyfile, None).unwrap();
+        let contents = fs::read_to_string(&file_path).unwrap();
+        assert!(!contents.contains("test-code-1-qzvx"));
+        assert!(contents.contains(code_encryption::ENCRYPTED_PREFIX));
+        
+        // Memory keeps plaintext; reload is transparent
+        assert_eq!(store.get_target("1").unwrap().code.expose(), "test-code-1-qzvx");
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(loaded.get_target("1").unwrap().code.expose(), "test-code-1-qzvx");
+        
+        // Back to plaintext
+        store.disable_code_encryption().unwrap();
+        assert!(fs::read_to_string(&file_path).unwrap().contains("test-code-1-qzvx"));
+    }
+    
+    #[test]
+    fn test_encrypted_codes_with_passphrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.add_target(create_test_target("1")).unwrap();
+        store.enable_code_encryption(KeySource::Passphrase, Some("hunter2")).unwrap();
+        
+        let err = SettingsStore::load_from_path(&file_path).err().unwrap();
+        assert!(err.downcast_ref::<code_encryption::SettingsLocked>().is_some());
+        assert!(SettingsStore::load_from_path_with_passphrase(&file_path, "wrong").is_err());
+        
+        let loaded = SettingsStore::load_from_path_with_passphrase(&file_path, "hunter2").unwrap();
+        assert_eq!(loaded.get_target("1").unwrap().code.expose(), "test-code-1-qzvx");
+    }
+    
+    #[test]
+    fn test_plaintext_codes_migrated_on_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.enable_code_encryption(KeySource::Keyfile, None).unwrap();
+        
+        // A hand-edited plaintext code in an encrypted file
+        let mut raw: serde_json::Value = serde_json::from_str(&fs::read_to_string(&file_path).unwrap()).unwrap();
+        raw["targets"] = serde_json::to_value(vec![create_test_target("1")]).unwrap();
+        fs::write(&file_path, raw.to_string()).unwrap();
+        
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(loaded.get_target("1").unwrap().code.expose(), "test-code-1-qzvx");
+        assert!(!fs::read_to_string(&file_path).unwrap().contains("test-code-1-qzvx"));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_save_creates_private_files() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("blink");
+        let file_path = config_dir.join("settings.json");
+        
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.add_target(create_test_target("1")).unwrap();
+        
+        let mode = |p: &Path| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&config_dir), 0o700);
+        assert_eq!(mode(&file_path), 0o600);
+        
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert!(loaded.security_warnings().is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_world_readable_settings_warn() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("blink").join("settings.json");
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.add_target(create_test_target("1")).unwrap();
+        
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o644)).unwrap();
+        
+        // Default: loads, but reports the problem
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(loaded.security_warnings().len(), 1);
+        assert!(matches!(loaded.security_warnings()[0], SecurityIssue::Accessible { .. }));
+        
+        // Saving tightens the file again
+        loaded.save().unwrap();
+        assert!(SettingsStore::load_from_path(&file_path).unwrap().security_warnings().is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_strict_permissions_refuses() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("blink").join("settings.json");
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.settings_mut().app_settings.strict_permissions = true;
+        store.save().unwrap();
+        
+        fs::set_permissions(temp_dir.path().join("blink"), fs::Permissions::from_mode(0o755)).unwrap();
+        
+        let result = SettingsStore::load_from_path(&file_path);
+        let err_msg = result.err().unwrap().to_string();
+        assert!(err_msg.contains("Refusing to load insecure settings"), "Error was: {}", err_msg);
+    }
+    
     // Edge case tests
     
+    #[test]
+    fn test_locked_store_refuses_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        fs::write(&file_path, "precious").unwrap();
+        
+        let mut store = SettingsStore::new_locked(&file_path);
+        assert!(store.is_locked());
+        assert!(store.save().is_err());
+        
+        let mut app_settings = store.settings().app_settings.clone();
+        app_settings.autostart = true;
+        let keybinds = store.settings().keybinds.clone();
+        let err = store.update_preferences(app_settings, keybinds).unwrap_err();
+        assert!(err.downcast_ref::<code_encryption::SettingsLocked>().is_some());
+        assert!(!store.settings().app_settings.autostart);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "precious");
+    }
+    
+    #[test]
+    fn test_locked_store_mutators_change_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        fs::write(&file_path, "precious").unwrap();
+        
+        let mut store = SettingsStore::new_locked(&file_path);
+        let mut seeded = create_test_target("1");
+        seeded.channels.push(Channel { id: "ch_1".into(), name: "standup".into() });
+        store.settings_mut().targets = vec![seeded, create_test_target("2")];
+        let before = store.settings().clone();
+        
+        let is_locked = |err: anyhow::Error| err.downcast_ref::<code_encryption::SettingsLocked>().is_some();
+        let mut updated = create_test_target("1");
+        updated.label = "Renamed".into();
+        let geometry = WindowGeometry { x: 0, y: 0, width: 800, height: 600, monitor: None };
+        let bundle = SettingsStore::new_with_path(temp_dir.path().join("other.json")).export_bundle(true);
+        
+        assert!(is_locked(store.create_target(NewTarget { label: "New".into(), ..Default::default() }).unwrap_err()));
+        assert!(is_locked(store.add_target(create_test_target("3")).unwrap_err()));
+        assert!(is_locked(store.remove_target("1").unwrap_err()));
+        assert!(is_locked(store.update_target(updated).unwrap_err()));
+        assert!(is_locked(store.record_recent_call("1", None).unwrap_err()));
+        assert!(is_locked(store.set_window_geometry("1", geometry).unwrap_err()));
+        assert!(is_locked(store.set_primary_target("2").unwrap_err()));
+        assert!(is_locked(store.reorder_targets(&["2".into(), "1".into()]).unwrap_err()));
+        assert!(is_locked(store.add_channel("1", "retro").unwrap_err()));
+        assert!(is_locked(store.remove_channel("1", "ch_1").unwrap_err()));
+        assert!(is_locked(store.merge_targets("1", "2").unwrap_err()));
+        assert!(is_locked(store.import_bundle(&bundle, &ImportOptions::default()).unwrap_err()));
+        assert!(is_locked(store.enable_code_encryption(KeySource::Keyfile, None).unwrap_err()));
+        assert!(is_locked(store.disable_code_encryption().unwrap_err()));
+        
+        assert_eq!(store.settings(), &before);
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "precious");
+        assert!(!temp_dir.path().join("settings.key").exists());
+    }
+    
+    #[test]
+    fn test_duplicate_ids_and_codes_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        
+        let err = store.add_target(create_test_target("1")).unwrap_err().to_string();
+        assert!(err.contains("already exists"), "Error was: {}", err);
+        
+        let mut same_code = create_test_target("2");
+        same_code.code = "test-code-1-qzvx".into();
+        let err = store.add_target(same_code).unwrap_err().to_string();
+        assert!(err.contains("already uses this pairing code"), "Error was: {}", err);
+        
+        // Updating onto another target's code is rejected too
+        store.add_target(create_test_target("3")).unwrap();
+        let mut third = store.get_target("3").unwrap().clone();
+        third.code = "test-code-1-qzvx".into();
+        assert!(store.update_target(third).is_err());
+        
+        let mut blank = create_test_target("4");
+        blank.label = "  ".into();
+        assert!(store.add_target(blank).is_err());
+        assert_eq!(store.get_targets().len(), 2);
+    }
+    
     #[test]
     fn test_empty_file_path() {
         // Empty path should create defaults since it's treated as non-existent file
@@ -450,4 +1426,343 @@ mod tests {
         let store = SettingsStore::load_from_path(&file_path).unwrap();
         assert!(store.settings().app_settings.always_on_top); // Should have default
     }
+    
+    #[test]
+    fn test_legacy_timestamps_load_with_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        let target = |id: &str, code: &str, created_at: &str| serde_json::json!({
+            "id": id, "label": id, "code": code, "type": "person",
+            "call_defaults": {}, "created_at": created_at
+        });
+        let json = serde_json::json!({
+            "version": 1,
+            "app_settings": {},
+            "keybinds": { "join_primary": "Cmd+J", "hangup": "Cmd+H" },
+            "targets": [
+                target("tg_demo", "code-1", "2024-03-01"),
+                target("tg_js", "code-2", "2024-03-01T10:00:00.000Z"),
+                target("tg_bad", "code-3", "yesterday"),
+            ]
+        });
+        fs::write(&file_path, json.to_string()).unwrap();
+        
+        let store = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(store.get_targets().len(), 3);
+        assert_eq!(store.get_target("tg_demo").unwrap().created_at.to_string(), "2024-03-01T00:00:00Z");
+        assert_eq!(store.get_target("tg_js").unwrap().created_at.to_string(), "2024-03-01T10:00:00Z");
+        
+        assert_eq!(store.data_warnings().len(), 1);
+        assert!(store.data_warnings()[0].contains("tg_bad"));
+        
+        // The bad value is preserved on save rather than silently replaced
+        store.save().unwrap();
+        let contents = fs::read_to_string(&file_path).unwrap();
+        assert!(contents.contains("\"yesterday\""));
+    }
+    
+    #[test]
+    fn test_link_target_requires_url() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        
+        let new_link = NewTarget { label: "Link".into(), target_type: TargetType::Link, ..Default::default() };
+        assert!(store.create_target(new_link).is_err());
+        assert!(store.get_targets().is_empty());
+    }
+    
+    #[test]
+    fn test_merge_requires_shared_room() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        store.add_target(create_test_target("2")).unwrap();
+        
+        assert!(store.merge_targets("1", "2").is_err());
+        assert!(store.merge_targets("1", "1").is_err());
+        assert!(store.merge_targets("1", "missing").is_err());
+        assert_eq!(store.get_targets().len(), 2);
+    }
+    
+    #[test]
+    fn test_prefix_like_code_survives_encryption() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.enable_code_encryption(KeySource::Keyfile, None).unwrap();
+        
+        // Looks like ciphertext but is what the user typed
+        let code = format!("{}our-room-qzvx", code_encryption::ENCRYPTED_PREFIX);
+        let mut target = create_test_target("1");
+        target.code = code.clone().into();
+        store.add_target(target).unwrap();
+        
+        let loaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(loaded.get_target("1").unwrap().code.expose(), &code);
+    }
+    
+    #[test]
+    fn test_same_room_conflict_is_structured() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        
+        // Differs only by hyphens -> same derived room
+        let mut near = create_test_target("2");
+        near.code = "testcode-1-qzvx".into();
+        let err = store.add_target(near).unwrap_err();
+        let conflict = err.downcast_ref::<TargetConflict>().expect("structured conflict");
+        assert_eq!(conflict.kind, ConflictKind::SameRoom);
+        assert_eq!(conflict.existing_id, "1");
+        assert_eq!(conflict.target_id, "2");
+    }
+    
+    #[test]
+    fn test_conflicts_reported_on_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        let mut settings = Settings::default();
+        let mut duplicate = create_test_target("2");
+        duplicate.code = "test-code-1-qzvx".into();
+        settings.targets = vec![create_test_target("1"), duplicate];
+        fs::write(&file_path, serde_json::to_string(&settings).unwrap()).unwrap();
+        
+        let store = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(store.conflicts().len(), 1);
+        assert_eq!(store.conflicts()[0].kind, ConflictKind::SameCode);
+        assert_eq!(store.data_warnings().len(), 1);
+        assert!(store.data_warnings()[0].contains("already uses this pairing code"));
+    }
+    
+    #[test]
+    fn test_merge_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        
+        let mut settings = Settings::default();
+        let mut keep = create_test_target("1");
+        keep.notes = Some("Office".into());
+        let mut other = create_test_target("2");
+        other.code = "testcode-1-qzvx".into();
+        other.notes = Some("Home".into());
+        other.is_primary = true;
+        settings.targets = vec![keep, other, create_test_target("3")];
+        settings.keybinds.target_hotkeys.insert("2".into(), "Ctrl+Alt+2".into());
+        fs::write(&file_path, serde_json::to_string(&settings).unwrap()).unwrap();
+        
+        let mut store = SettingsStore::load_from_path(&file_path).unwrap();
+        let merged = store.merge_targets("1", "2").unwrap();
+        assert_eq!(merged.label, "Test 1 / Test 2");
+        assert_eq!(merged.notes.as_deref(), Some("Office\n\nHome"));
+        assert!(merged.is_primary);
+        assert_eq!(merged.code.expose(), "test-code-1-qzvx");
+        
+        let reloaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert!(reloaded.get_target("2").is_none());
+        assert!(reloaded.conflicts().is_empty());
+        let hotkeys = &reloaded.settings().keybinds.target_hotkeys;
+        assert_eq!(hotkeys.get("1").map(String::as_str), Some("Ctrl+Alt+2"));
+        assert!(!hotkeys.contains_key("2"));
+    }
+    
+    #[test]
+    fn test_channels() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("settings.json");
+        let mut store = SettingsStore::new_with_path(&file_path);
+        store.add_target(create_test_target("1")).unwrap();
+        
+        let standup = store.add_channel("1", " Standup ").unwrap();
+        assert!(standup.id.starts_with("ch_"));
+        assert_eq!(standup.name, "Standup");
+        let pairing = store.add_channel("1", "pairing").unwrap();
+        
+        // Names are unique per target after normalization
+        assert!(store.add_channel("1", "standup").is_err());
+        assert!(store.add_channel("1", "  ").is_err());
+        assert!(store.add_channel("missing", "x").is_err());
+        
+        let (target, channel) = store.find_channel(&pairing.id).unwrap();
+        assert_eq!((target.id.as_str(), channel.name.as_str()), ("1", "pairing"));
+        
+        let reloaded = SettingsStore::load_from_path(&file_path).unwrap();
+        assert_eq!(reloaded.get_target("1").unwrap().channels.len(), 2);
+        
+        // Dropping a channel drops its hotkey
+        store.settings.keybinds.channel_hotkeys.insert(standup.id.clone(), "Ctrl+Alt+S".into());
+        store.settings.keybinds.channel_hotkeys.insert(pairing.id.clone(), "Ctrl+Alt+P".into());
+        assert!(store.remove_channel("1", &standup.id).unwrap());
+        assert!(!store.remove_channel("1", &standup.id).unwrap());
+        assert!(!store.settings().keybinds.channel_hotkeys.contains_key(&standup.id));
+        
+        store.remove_target("1").unwrap();
+        assert!(store.settings().keybinds.channel_hotkeys.is_empty());
+    }
+    
+    #[test]
+    fn test_update_target_assigns_channel_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        let standup = store.add_channel("1", "standup").unwrap();
+        store.settings.keybinds.channel_hotkeys.insert(standup.id.clone(), "Ctrl+Alt+S".into());
+        
+        // The UI sends channels without ids for new names
+        let mut target = store.get_target("1").unwrap().clone();
+        target.channels = vec![Channel { id: String::new(), name: "retro".into() }];
+        store.update_target(target).unwrap();
+        
+        let channels = &store.get_target("1").unwrap().channels;
+        assert_eq!(channels.len(), 1);
+        assert!(channels[0].id.starts_with("ch_"));
+        assert!(store.settings().keybinds.channel_hotkeys.is_empty());
+    }
+    
+    #[test]
+    fn test_weak_codes_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        
+        let mut weak = create_test_target("1");
+        weak.code = "password123".into();
+        let err = store.add_target(weak.clone()).unwrap_err().to_string();
+        assert!(err.contains("too weak"), "Error was: {}", err);
+        
+        let mut memorable = create_test_target("2");
+        memorable.code = "alice-bob-standup".into();
+        store.add_target(memorable.clone()).unwrap();
+        
+        // The threshold is configurable both ways
+        store.settings.app_settings.min_code_strength = CodeStrength::Strong;
+        memorable.label = "Renamed".into();
+        store.update_target(memorable.clone()).unwrap();
+        memorable.code = "alice-bob-retro".into();
+        assert!(store.update_target(memorable).is_err());
+        
+        store.settings.app_settings.min_code_strength = CodeStrength::Weak;
+        store.add_target(weak).unwrap();
+    }
+    
+    #[test]
+    fn test_window_geometry() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("settings.json");
+        let mut store = SettingsStore::new_with_path(&path);
+        let target = create_test_target("1");
+        store.add_target(target.clone()).unwrap();
+        
+        let geometry = WindowGeometry { x: 1980, y: 40, width: 800, height: 600, monitor: Some("HDMI-1".into()) };
+        assert!(store.set_window_geometry("1", geometry.clone()).unwrap());
+        assert!(!store.set_window_geometry("missing", geometry.clone()).unwrap());
+        
+        // Edits from the settings UI (loaded before the call) keep the geometry
+        let mut edited = target;
+        edited.label = "Renamed".into();
+        store.update_target(edited).unwrap();
+        
+        let reloaded = SettingsStore::load_from_path(&path).unwrap();
+        assert_eq!(reloaded.get_target("1").unwrap().call_defaults.window, Some(geometry));
+    }
+    
+    #[test]
+    fn test_update_preferences() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("settings.json");
+        let mut store = SettingsStore::new_with_path(&path);
+        
+        // A copy taken before the target was added
+        let stale = store.settings().clone();
+        store.add_target(create_test_target("1")).unwrap();
+        
+        let mut app_settings = stale.app_settings;
+        app_settings.always_on_top = false;
+        let mut keybinds = stale.keybinds;
+        keybinds.hangup = "Ctrl+Alt+H".into();
+        store.update_preferences(app_settings, keybinds).unwrap();
+        
+        let reloaded = SettingsStore::load_from_path(&path).unwrap();
+        assert!(!reloaded.settings().app_settings.always_on_top);
+        assert_eq!(reloaded.settings().keybinds.hangup, "Ctrl+Alt+H");
+        assert!(reloaded.get_target("1").is_some());
+    }
+    
+    #[test]
+    fn test_recent_calls() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        for i in 0..7 {
+            store.add_target(create_test_target(&i.to_string())).unwrap();
+        }
+        let channel = store.add_channel("0", "standup").unwrap();
+        
+        for i in 0..7 {
+            assert!(store.record_recent_call(&i.to_string(), None).unwrap());
+        }
+        assert!(store.record_recent_call("0", Some(&channel.id)).unwrap());
+        // Joining again moves to the front instead of duplicating
+        assert!(store.record_recent_call("5", None).unwrap());
+        
+        let recent: Vec<(String, Option<String>)> = store.recent_calls().iter()
+            .map(|(t, c)| (t.id.clone(), c.map(|c| c.name.clone())))
+            .collect();
+        assert_eq!(recent, [
+            ("5".to_string(), None),
+            ("0".to_string(), Some("standup".to_string())),
+            ("6".to_string(), None),
+            ("4".to_string(), None),
+            ("3".to_string(), None),
+        ]);
+    }
+    
+    #[test]
+    fn test_recent_calls_skip_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        store.add_target(create_test_target("1")).unwrap();
+        store.add_target(create_test_target("2")).unwrap();
+        let channel = store.add_channel("1", "pairing").unwrap();
+        
+        assert!(!store.record_recent_call("missing", None).unwrap());
+        assert!(!store.record_recent_call("2", Some(&channel.id)).unwrap());
+        store.record_recent_call("1", Some(&channel.id)).unwrap();
+        store.record_recent_call("2", None).unwrap();
+        
+        store.remove_channel("1", &channel.id).unwrap();
+        store.remove_target("2").unwrap();
+        assert!(store.recent_calls().is_empty());
+    }
+    
+    #[test]
+    fn test_link_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = SettingsStore::new_with_path(temp_dir.path().join("settings.json"));
+        
+        let link = store.create_target(NewTarget {
+            label: "Weekly".into(),
+            code: Some(" Meet.Google.com/abc-defg-hij ".into()),
+            target_type: TargetType::Link,
+            ..Default::default()
+        }).unwrap();
+        // Stored normalized; provider URLs skip the code strength check
+        assert_eq!(link.code.expose(), "https://meet.google.com/abc-defg-hij");
+        assert_eq!(link.meeting_url(), "https://meet.google.com/abc-defg-hij");
+        
+        let mut invalid = link.clone();
+        invalid.code = "javascript:alert(1)".into();
+        assert!(store.update_target(invalid).is_err());
+        
+        let mut with_channel = link.clone();
+        with_channel.channels.push(Channel { id: String::new(), name: "standup".into() });
+        assert!(store.update_target(with_channel).is_err());
+        
+        let err = store.create_target(NewTarget {
+            label: "Same".into(),
+            code: Some("https://meet.google.com/abc-defg-hij".into()),
+            target_type: TargetType::Link,
+            ..Default::default()
+        }).unwrap_err();
+        assert!(err.downcast_ref::<TargetConflict>().is_some());
+    }
 }